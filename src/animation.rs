@@ -1,37 +1,450 @@
-use rand::prelude::SliceRandom;
-use std::ops::Deref;
-use std::sync::LazyLock;
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::fs;
+use std::sync::{Arc, LazyLock, RwLock};
+use std::time::SystemTime;
+use tokio::signal::unix::{signal, SignalKind};
+use tracing::{error, info};
 
-const ANIMATIONS_FILE_NAME: &str = "animation_urls.json";
-static ANIMATION_URLS: LazyLock<Vec<String>> = LazyLock::new(|| {
-    let file_path = ANIMATIONS_FILE_NAME;
-    let json_string: &str = &std::fs::read_to_string(file_path)
-        .expect(format!("Error. Unable to read file {}", file_path).as_str());
-    let animation_urls: Vec<String> = serde_json::from_str(json_string)
-        .expect(format!("Error. Unable to parse json in {}", file_path).as_str());
-    if animation_urls.is_empty() {
-        panic!("Error. Animation urls list cannot be empty");
+use crate::{config, file_helper};
+
+const DEFAULT_WEIGHT: u32 = 1;
+const FALLBACK_ID: &str = "animation";
+pub const MAX_WEIGHT: u32 = 1_000_000;
+/// The random choice sums the weights in a u32.
+const MAX_TOTAL_WEIGHT: u64 = u32::MAX as u64;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Animation {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    pub url: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    #[serde(default = "default_weight")]
+    pub weight: u32,
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl Animation {
+    pub fn from_url(url: &str) -> Self {
+        let name = url
+            .rsplit('/')
+            .find(|segment| !segment.is_empty())
+            .map(|file_name| file_name.split('.').next().unwrap_or(file_name))
+            .filter(|name| !name.is_empty())
+            .unwrap_or(url)
+            .to_string();
+
+        let id = match to_id(&name) {
+            id if id.is_empty() => FALLBACK_ID.to_string(),
+            id => id,
+        };
+
+        Animation {
+            id,
+            name,
+            url: url.to_string(),
+            tags: Vec::new(),
+            weight: DEFAULT_WEIGHT,
+            enabled: true,
+        }
+    }
+
+    pub fn is_selectable(&self) -> bool {
+        self.enabled && self.weight > 0
+    }
+}
+
+fn default_weight() -> u32 {
+    DEFAULT_WEIGHT
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnimationEntry {
+    Url(String),
+    Animation(Animation),
+}
+
+static ANIMATIONS: LazyLock<RwLock<Arc<Vec<Animation>>>> =
+    LazyLock::new(|| RwLock::new(Arc::new(Vec::new())));
+
+pub fn load_animations() -> Result<(), String> {
+    let animations = read_animations(&config::animation_file())?;
+    swap_animations(animations);
+
+    Ok(())
+}
+
+pub fn start_animations_watcher() {
+    tokio::spawn(async {
+        let file_path = config::animation_file();
+        let mut last_modified = file_modified_time(&file_path);
+        let mut interval = tokio::time::interval(config::animation_reload_interval());
+        let mut sighup =
+            signal(SignalKind::hangup()).expect("Error. Could not listen to SIGHUP signal");
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    let modified = file_modified_time(&file_path);
+                    if modified == last_modified {
+                        continue;
+                    }
+                    last_modified = modified;
+                    info!(file_path, "Animations file changed");
+                }
+                _ = sighup.recv() => {
+                    info!(file_path, "Received SIGHUP");
+                }
+            }
+
+            reload_animations(&file_path);
+        }
+    });
+}
+
+fn reload_animations(file_path: &str) {
+    match read_animations(file_path) {
+        Ok(animations) => {
+            let animations_size = animations.len();
+            swap_animations(animations);
+
+            info!(file_path, animations_size, "Reloaded animations");
+        }
+        Err(why) => {
+            error!(
+                file_path,
+                trace = why,
+                "Could not reload animations, keeping the current list"
+            );
+        }
+    }
+}
+
+fn read_animations(file_path: &str) -> Result<Vec<Animation>, String> {
+    let json_string: &str = &fs::read_to_string(file_path)
+        .map_err(|why| format!("Unable to read file {}. {:?}", file_path, why))?;
+    let animation_entries: Vec<AnimationEntry> = serde_json::from_str(json_string)
+        .map_err(|why| format!("Unable to parse json in {}. {}", file_path, why))?;
+
+    // Ids derived for bare urls are made unique against the structured ones.
+    let mut taken_ids: HashSet<String> = animation_entries
+        .iter()
+        .filter_map(|animation_entry| match animation_entry {
+            AnimationEntry::Animation(animation) => Some(animation.id.clone()),
+            AnimationEntry::Url(_) => None,
+        })
+        .collect();
+
+    let mut animations: Vec<Animation> = Vec::with_capacity(animation_entries.len());
+    for animation_entry in animation_entries {
+        let mut animation = match animation_entry {
+            AnimationEntry::Url(url) => {
+                let mut animation = Animation::from_url(&url);
+                animation.id = unique_id(&animation.id, |candidate_id| {
+                    taken_ids.contains(candidate_id)
+                });
+                taken_ids.insert(animation.id.clone());
+                animation
+            }
+            AnimationEntry::Animation(animation) => animation,
+        };
+        if animation.name.trim().is_empty() {
+            animation.name = animation.id.clone();
+        }
+        animations.push(animation);
+    }
+
+    validate_animations(&animations)?;
+
+    Ok(animations)
+}
+
+fn validate_animations(animations: &[Animation]) -> Result<(), String> {
+    if animations.is_empty() {
+        return Err("Animations list cannot be empty".to_string());
     }
 
-    animation_urls
-});
+    let mut ids: HashSet<&str> = HashSet::new();
+    for animation in animations {
+        if animation.id.trim().is_empty() {
+            return Err(format!("Animation has an empty id. Url: {}", animation.url));
+        }
+        if animation.url.trim().is_empty() {
+            return Err(format!("Animation has an empty url. Id: {}", animation.id));
+        }
+        if !ids.insert(animation.id.as_str()) {
+            return Err(format!("Animation id is duplicated: {}", animation.id));
+        }
+        if animation.weight > MAX_WEIGHT {
+            return Err(format!(
+                "Animation weight cannot be more than {}. Id: {}",
+                MAX_WEIGHT, animation.id
+            ));
+        }
+    }
+
+    let total_weight: u64 = animations
+        .iter()
+        .filter(|animation| animation.is_selectable())
+        .map(|animation| u64::from(animation.weight))
+        .sum();
+    if total_weight > MAX_TOTAL_WEIGHT {
+        return Err(format!(
+            "Total weight of the enabled animations cannot be more than {}",
+            MAX_TOTAL_WEIGHT
+        ));
+    }
 
-pub fn get_animation_urls_size() -> usize {
-    ANIMATION_URLS.deref().len()
+    if !animations.iter().any(Animation::is_selectable) {
+        return Err("At least one animation must be enabled and have a weight".to_string());
+    }
+
+    Ok(())
 }
 
-pub fn get_animation_urls() -> Vec<String> {
-    ANIMATION_URLS.deref().to_owned()
+pub fn add_animation(mut animation: Animation) -> Result<usize, String> {
+    let mut animations_guard = ANIMATIONS.write().unwrap();
+
+    if animations_guard
+        .iter()
+        .any(|existing_animation| existing_animation.url == animation.url)
+    {
+        return Err(format!("Animation url already exists: {}", animation.url));
+    }
+    animation.id = unique_id(&animation.id, |candidate_id| {
+        animations_guard
+            .iter()
+            .any(|existing_animation| existing_animation.id == candidate_id)
+    });
+
+    let mut animations = animations_guard.to_vec();
+    animations.push(animation);
+    validate_animations(&animations)?;
+    write_animations(&config::animation_file(), &animations)?;
+
+    let index = animations.len() - 1;
+    *animations_guard = Arc::new(animations);
+
+    Ok(index)
+}
+
+pub fn remove_animation(index: usize) -> Result<Animation, String> {
+    let mut animations_guard = ANIMATIONS.write().unwrap();
+
+    if index >= animations_guard.len() {
+        return Err(format!(
+            "Index {} does not exist. Use a value between {} and {}",
+            index,
+            0,
+            animations_guard.len() - 1
+        ));
+    }
+
+    let mut animations = animations_guard.to_vec();
+    let removed_animation = animations.remove(index);
+    validate_animations(&animations)
+        .map_err(|why| format!("The animation cannot be removed. {}", why))?;
+    write_animations(&config::animation_file(), &animations)?;
+
+    *animations_guard = Arc::new(animations);
+
+    Ok(removed_animation)
+}
+
+fn write_animations(file_path: &str, animations: &[Animation]) -> Result<(), String> {
+    let json_string = serde_json::to_string_pretty(animations)
+        .map_err(|why| format!("Unable to serialize animations. {}", why))?;
+
+    file_helper::write_atomically(file_path, json_string.as_bytes())
+        .map_err(|why| format!("Unable to write file {}. {:?}", file_path, why))
+}
+
+pub fn to_id(name: &str) -> String {
+    name.trim()
+        .to_lowercase()
+        .split(|character: char| !character.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join("-")
+}
+
+fn unique_id(id: &str, is_taken: impl Fn(&str) -> bool) -> String {
+    if !is_taken(id) {
+        return id.to_string();
+    }
+
+    (2..)
+        .map(|suffix| format!("{}-{}", id, suffix))
+        .find(|candidate_id| !is_taken(candidate_id))
+        .unwrap()
+}
+
+fn file_modified_time(file_path: &str) -> Option<SystemTime> {
+    fs::metadata(file_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn swap_animations(animations: Vec<Animation>) {
+    *ANIMATIONS.write().unwrap() = Arc::new(animations);
+}
+
+fn current_animations() -> Arc<Vec<Animation>> {
+    ANIMATIONS.read().unwrap().clone()
+}
+
+pub fn get_animations_size() -> usize {
+    current_animations().len()
+}
+
+pub fn get_animations() -> Vec<Animation> {
+    current_animations().to_vec()
 }
 
 pub fn get_random_animation_url() -> String {
+    let animations = current_animations();
+    let selectable_animations: Vec<&Animation> = animations
+        .iter()
+        .filter(|animation| animation.is_selectable())
+        .collect();
+
+    let weighted_index = WeightedIndex::new(
+        selectable_animations
+            .iter()
+            .map(|animation| animation.weight),
+    )
+    .expect("Error. Animations are validated to have at least one weighted animation");
+
     let mut rng = rand::thread_rng();
-    ANIMATION_URLS.deref().choose(&mut rng).unwrap().to_string()
+    selectable_animations[weighted_index.sample(&mut rng)]
+        .url
+        .to_string()
+}
+
+pub fn get_animation(index: usize) -> Option<Animation> {
+    current_animations().get(index).cloned()
+}
+
+pub fn find_animation(id_or_name: &str) -> Option<(usize, Animation)> {
+    let id_or_name = id_or_name.trim();
+
+    current_animations()
+        .iter()
+        .enumerate()
+        .find(|(_, animation)| {
+            animation.id.eq_ignore_ascii_case(id_or_name)
+                || animation.name.eq_ignore_ascii_case(id_or_name)
+        })
+        .map(|(index, animation)| (index, animation.clone()))
+}
+
+pub fn search_animations(query: &str, limit: usize) -> Vec<(usize, Animation)> {
+    let query = query.trim().to_lowercase();
+
+    current_animations()
+        .iter()
+        .enumerate()
+        .filter(|(_, animation)| animation.enabled)
+        .filter(|(index, animation)| {
+            index.to_string().starts_with(&query)
+                || animation.name.to_lowercase().contains(&query)
+                || animation.id.to_lowercase().contains(&query)
+                || animation
+                    .tags
+                    .iter()
+                    .any(|tag| tag.to_lowercase().contains(&query))
+        })
+        .take(limit)
+        .map(|(index, animation)| (index, animation.clone()))
+        .collect()
 }
 
-pub fn get_animation_url(index: usize) -> Option<String> {
-    ANIMATION_URLS
-        .deref()
-        .get(index)
-        .map(|animation_url| animation_url.to_owned())
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{env, process};
+
+    fn read_animations_from(file_name: &str, json_string: &str) -> Result<Vec<Animation>, String> {
+        let file_path = env::temp_dir().join(format!("hoseus_bot_{}_{}", process::id(), file_name));
+        fs::write(&file_path, json_string).unwrap();
+
+        let animations = read_animations(file_path.to_str().unwrap());
+        fs::remove_file(&file_path).unwrap();
+
+        animations
+    }
+
+    #[test]
+    fn reads_plain_array_of_urls() {
+        let animations = read_animations_from(
+            "plain.json",
+            r#"[
+                "https://media.tenor.com/a/after-work.gif",
+                "https://media.tenor.com/b/after-work.gif",
+                "https://media.tenor.com/c/___.gif",
+                "https://media.tenor.com/d/-.gif"
+            ]"#,
+        )
+        .unwrap();
+
+        let ids: Vec<&str> = animations
+            .iter()
+            .map(|animation| animation.id.as_str())
+            .collect();
+        assert_eq!(
+            ids,
+            ["after-work", "after-work-2", "animation", "animation-2"]
+        );
+        assert!(animations
+            .iter()
+            .all(|animation| animation.enabled && animation.weight == DEFAULT_WEIGHT));
+        assert_eq!(animations[0].name, "after-work");
+        assert_eq!(animations[2].name, "___");
+    }
+
+    #[test]
+    fn reads_mix_of_urls_and_records() {
+        let animations = read_animations_from(
+            "mixed.json",
+            r#"[
+                "https://media.tenor.com/a/after-work.gif",
+                { "id": "after-work", "name": "After work", "url": "https://media.tenor.com/b/x.gif", "tags": ["work"], "weight": 2 },
+                { "id": "party", "url": "https://media.tenor.com/c/party.gif", "enabled": false }
+            ]"#,
+        )
+        .unwrap();
+
+        let ids: Vec<&str> = animations
+            .iter()
+            .map(|animation| animation.id.as_str())
+            .collect();
+        assert_eq!(ids, ["after-work-2", "after-work", "party"]);
+        assert_eq!(animations[1].name, "After work");
+        assert_eq!(animations[1].weight, 2);
+        assert_eq!(animations[2].name, "party");
+        assert!(!animations[2].enabled);
+    }
+
+    #[test]
+    fn rejects_weights_over_the_cap() {
+        let why = read_animations_from(
+            "heavy.json",
+            r#"[{ "id": "heavy", "url": "https://media.tenor.com/a/heavy.gif", "weight": 4294967295 }]"#,
+        )
+        .err()
+        .unwrap();
+
+        assert!(why.contains("weight"), "{}", why);
+    }
 }
@@ -1,61 +1,362 @@
 use serenity::all::{
-    CommandDataOption, CommandInteraction, CommandOptionType, Context, CreateCommand,
-    CreateCommandOption, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseMessage,
+    ButtonStyle, CommandInteraction, CommandOptionType, ComponentInteraction, Context,
+    CreateActionRow, CreateButton, CreateCommand, CreateCommandOption, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage, EditInteractionResponse,
 };
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tracing::{info, warn};
 
-use crate::animation;
+use crate::animation::Animation;
+use crate::commands::command_error::CommandError;
+use crate::commands::command_options::CommandOptions;
 use crate::commands::serenity_command_helper;
+use crate::commands::serenity_command_helper::respond_success_interaction;
+use crate::commands::slash_command::SlashCommand;
+use crate::{animation, config};
 
 pub const COMMAND_NAME: &str = "animations";
 
-pub fn register() -> CreateCommand {
+const LIST_SUB_COMMAND_NAME: &str = "list";
+const ADD_SUB_COMMAND_NAME: &str = "add";
+const REMOVE_SUB_COMMAND_NAME: &str = "remove";
+
+pub const LIST_COMPONENT_ID_PREFIX: &str = "animations_list";
+
+const LIST_PAGE_SIZE: usize = 5;
+const LIST_BUTTONS_TIMEOUT: Duration = Duration::from_secs(5 * 60);
+
+pub struct AnimationsCommand;
+
+#[serenity::async_trait]
+impl SlashCommand for AnimationsCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register()
+    }
+
+    async fn run(&self, ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError> {
+        run(ctx, command).await
+    }
+}
+
+fn register() -> CreateCommand {
     CreateCommand::new(COMMAND_NAME)
-        .description("Notify everyone via text channel")
+        .description("List and manage the animations sent to telegram")
         .add_option(CreateCommandOption::new(
             CommandOptionType::SubCommand,
-            "list",
+            LIST_SUB_COMMAND_NAME,
             "List all animation urls",
         ))
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                ADD_SUB_COMMAND_NAME,
+                "Add an animation url",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "url",
+                    "Url of the animation to add",
+                )
+                .required(true),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::String,
+                    "name",
+                    "Display name, taken from the url when not set",
+                )
+                .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(CommandOptionType::String, "tags", "Comma separated tags")
+                    .required(false),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "weight",
+                    "How likely the animation is picked at random, 1 by default",
+                )
+                .min_int_value(0)
+                .max_int_value(u64::from(animation::MAX_WEIGHT))
+                .required(false),
+            ),
+        )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                REMOVE_SUB_COMMAND_NAME,
+                "Remove an animation url",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::Integer,
+                    "index",
+                    "Index of the animation to remove",
+                )
+                .min_int_value(0)
+                .required(true),
+            ),
+        )
+}
+
+async fn run(ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError> {
+    let (sub_command_name, sub_options) =
+        CommandOptions::new(&command.data.options).sub_command()?;
+
+    match sub_command_name {
+        LIST_SUB_COMMAND_NAME => run_list(ctx, command).await,
+        ADD_SUB_COMMAND_NAME | REMOVE_SUB_COMMAND_NAME if !can_manage_animations(command) => {
+            Err(CommandError::NotAllowed(
+                "Managing animations requires a manager role or Manage Server".to_string(),
+            ))
+        }
+        ADD_SUB_COMMAND_NAME => run_add(ctx, command, &sub_options).await,
+        REMOVE_SUB_COMMAND_NAME => run_remove(ctx, command, &sub_options).await,
+        _ => Err(CommandError::Internal(format!(
+            "Unknown sub command: {}",
+            sub_command_name
+        ))),
+    }
 }
 
-pub async fn run(ctx: &Context, command: &CommandInteraction) -> Result<(), ()> {
-    let options: &[CommandDataOption] = command.data.options.as_slice();
+async fn run_add(
+    ctx: &Context,
+    command: &CommandInteraction,
+    sub_options: &CommandOptions<'_>,
+) -> Result<(), CommandError> {
+    let animation_url: &str = sub_options.required_string("url")?;
 
-    let sub_command_list: &CommandDataOption = options.get(0).unwrap();
+    if !is_http_url(animation_url) {
+        return Err(CommandError::UserInput(format!(
+            "Invalid url: {}",
+            animation_url
+        )));
+    }
 
-    match sub_command_list.kind() {
-        CommandOptionType::SubCommand => (),
-        _ => return respond_fail_interaction(ctx, command, "Invalid command").await,
+    let mut new_animation = Animation::from_url(animation_url);
+    if let Some(name) = sub_options.string("name")? {
+        new_animation.id = animation::to_id(name);
+        new_animation.name = name.trim().to_string();
     }
+    if let Some(tags) = sub_options.string("tags")? {
+        new_animation.tags = tags
+            .split(',')
+            .map(str::trim)
+            .filter(|tag| !tag.is_empty())
+            .map(str::to_lowercase)
+            .collect();
+    }
+    if let Some(weight) = sub_options.unsigned::<u32>("weight")? {
+        new_animation.weight = weight;
+    }
+    if new_animation.id.is_empty() {
+        return Err(CommandError::UserInput("Invalid name".to_string()));
+    }
+
+    match animation::add_animation(new_animation) {
+        Ok(index) => {
+            info!(index, animation_url, "Added animation");
 
-    respond_success_interaction(ctx, command, animation::get_animation_urls()).await
+            respond_success_interaction(
+                ctx,
+                command,
+                format!("Added animation with index {}", index).as_str(),
+            )
+            .await
+        }
+        Err(why) => Err(CommandError::UserInput(why)),
+    }
 }
 
-async fn respond_success_interaction(
+async fn run_remove(
     ctx: &Context,
     command: &CommandInteraction,
-    animation_urls: Vec<String>,
-) -> Result<(), ()> {
+    sub_options: &CommandOptions<'_>,
+) -> Result<(), CommandError> {
+    let index: usize = sub_options.required_unsigned("index")?;
+
+    match animation::remove_animation(index) {
+        Ok(removed_animation) => {
+            info!(
+                index,
+                animation_url = removed_animation.url,
+                "Removed animation"
+            );
+
+            respond_success_interaction(
+                ctx,
+                command,
+                format!("Removed animation {}: {}", index, removed_animation.name).as_str(),
+            )
+            .await
+        }
+        Err(why) => Err(CommandError::UserInput(why)),
+    }
+}
+
+fn can_manage_animations(command: &CommandInteraction) -> bool {
+    let Some(member) = command.member.as_ref() else {
+        return false;
+    };
+
+    let has_manage_guild_permission = member
+        .permissions
+        .is_some_and(|permissions| permissions.manage_guild());
+    let has_manager_role = member
+        .roles
+        .iter()
+        .any(|role_id| config::animation_manager_role_ids().contains(role_id));
+
+    has_manage_guild_permission || has_manager_role
+}
+
+fn is_http_url(value: &str) -> bool {
+    reqwest::Url::parse(value)
+        .is_ok_and(|url| matches!(url.scheme(), "http" | "https") && url.host().is_some())
+}
+
+async fn run_list(ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError> {
+    let issued_at_unix_seconds = unix_seconds_now();
+
     serenity_command_helper::respond_interaction(ctx, command, || {
-        CreateInteractionResponse::Message(
-            CreateInteractionResponseMessage::new().add_embeds(
-                animation_urls
-                    .iter()
-                    .enumerate()
-                    .map(|(index, animation_url)| {
-                        CreateEmbed::new().title(index.to_string()).image(animation_url)
-                    })
-                    .collect(),
-            ),
+        CreateInteractionResponse::Message(build_list_page(0, issued_at_unix_seconds))
+    })
+    .await?;
+
+    let ctx = ctx.clone();
+    let command = command.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(LIST_BUTTONS_TIMEOUT).await;
+
+        if let Err(why) = command
+            .edit_response(&ctx, EditInteractionResponse::new().components(Vec::new()))
+            .await
+        {
+            warn!(trace = ?why, "Could not expire animations list buttons");
+        }
+    });
+
+    Ok(())
+}
+
+/// Custom id: `animations_list:<page>:<issued_at_unix_seconds>`.
+pub async fn run_list_component(
+    ctx: &Context,
+    component: &ComponentInteraction,
+) -> Result<(), CommandError> {
+    let Some((page, issued_at_unix_seconds)) = parse_list_component_id(&component.data.custom_id)
+    else {
+        return serenity_command_helper::respond_component_interaction_with_ephemeral_string(
+            ctx,
+            component,
+            "Invalid button",
         )
+        .await;
+    };
+
+    if unix_seconds_now().saturating_sub(issued_at_unix_seconds) > LIST_BUTTONS_TIMEOUT.as_secs() {
+        return serenity_command_helper::respond_component_interaction(ctx, component, || {
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content("These buttons have expired, run the command again")
+                    .components(Vec::new()),
+            )
+        })
+        .await;
+    }
+
+    serenity_command_helper::respond_component_interaction(ctx, component, || {
+        CreateInteractionResponse::UpdateMessage(build_list_page(page, issued_at_unix_seconds))
     })
     .await
 }
 
-async fn respond_fail_interaction(
-    ctx: &Context,
-    command: &CommandInteraction,
-    error_message: &str,
-) -> Result<(), ()> {
-    serenity_command_helper::respond_interaction_with_string(ctx, command, error_message).await
+fn build_list_page(page: usize, issued_at_unix_seconds: u64) -> CreateInteractionResponseMessage {
+    let animations = animation::get_animations();
+    let pages_count = animations.len().div_ceil(LIST_PAGE_SIZE).max(1);
+    let page = page.min(pages_count - 1);
+
+    let embeds: Vec<CreateEmbed> = animations
+        .iter()
+        .enumerate()
+        .skip(page * LIST_PAGE_SIZE)
+        .take(LIST_PAGE_SIZE)
+        .map(|(index, animation)| build_animation_embed(index, animation))
+        .collect();
+
+    let previous_button = CreateButton::new(build_list_component_id(
+        page.saturating_sub(1),
+        issued_at_unix_seconds,
+    ))
+    .label("Previous")
+    .style(ButtonStyle::Secondary)
+    .disabled(page == 0);
+    // Discord rejects duplicated custom ids in a message, hence the different page on the
+    // disabled button of the last page.
+    let next_button = CreateButton::new(build_list_component_id(
+        (page + 1).min(pages_count),
+        issued_at_unix_seconds,
+    ))
+    .label("Next")
+    .style(ButtonStyle::Secondary)
+    .disabled(page + 1 >= pages_count);
+
+    CreateInteractionResponseMessage::new()
+        .content(format!("Page {}/{}", page + 1, pages_count))
+        .embeds(embeds)
+        .components(vec![CreateActionRow::Buttons(vec![
+            previous_button,
+            next_button,
+        ])])
+}
+
+fn build_animation_embed(index: usize, animation: &Animation) -> CreateEmbed {
+    let tags = if animation.tags.is_empty() {
+        "-".to_string()
+    } else {
+        animation.tags.join(", ")
+    };
+
+    CreateEmbed::new()
+        .title(format!("{}. {}", index, animation.name))
+        .description(format!(
+            "Id: {}\nTags: {}\nWeight: {}{}",
+            animation.id,
+            tags,
+            animation.weight,
+            if animation.enabled { "" } else { "\nDisabled" }
+        ))
+        .image(&animation.url)
+}
+
+fn build_list_component_id(page: usize, issued_at_unix_seconds: u64) -> String {
+    format!(
+        "{}:{}:{}",
+        LIST_COMPONENT_ID_PREFIX, page, issued_at_unix_seconds
+    )
+}
+
+fn parse_list_component_id(custom_id: &str) -> Option<(usize, u64)> {
+    let mut parts = custom_id.split(':');
+
+    if parts.next() != Some(LIST_COMPONENT_ID_PREFIX) {
+        return None;
+    }
+    let page = parts.next()?.parse::<usize>().ok()?;
+    let issued_at_unix_seconds = parts.next()?.parse::<u64>().ok()?;
+
+    Some((page, issued_at_unix_seconds))
+}
+
+fn unix_seconds_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
 }
@@ -0,0 +1,101 @@
+use serenity::all::{
+    CommandInteraction, ComponentInteraction, GuildId, Member, Permissions, UserId,
+};
+use std::fmt;
+
+use crate::config;
+use crate::config::CommandAccessRule;
+
+pub enum AccessDenial {
+    DeniedUser,
+    MissingPermissions(Permissions),
+    MissingRole,
+}
+
+impl fmt::Display for AccessDenial {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AccessDenial::DeniedUser => write!(f, "user is denied"),
+            AccessDenial::MissingPermissions(permissions) => {
+                write!(f, "missing permissions {}", permissions)
+            }
+            AccessDenial::MissingRole => write!(f, "missing an allowed role"),
+        }
+    }
+}
+
+pub fn check_command_access(command: &CommandInteraction) -> Result<(), AccessDenial> {
+    check_access(
+        &command.data.name,
+        command.guild_id,
+        command.user.id,
+        command.member.as_deref(),
+    )
+}
+
+pub fn check_component_access(
+    component: &ComponentInteraction,
+    command_name: &str,
+) -> Result<(), AccessDenial> {
+    check_access(
+        command_name,
+        component.guild_id,
+        component.user.id,
+        component.member.as_ref(),
+    )
+}
+
+fn check_access(
+    command_name: &str,
+    guild_id: Option<GuildId>,
+    user_id: UserId,
+    member: Option<&Member>,
+) -> Result<(), AccessDenial> {
+    let Some(access_rule) = find_access_rule(command_name, guild_id) else {
+        return Ok(());
+    };
+
+    if access_rule.denied_user_ids.contains(&user_id) {
+        return Err(AccessDenial::DeniedUser);
+    }
+
+    let member_permissions = member
+        .and_then(|member| member.permissions)
+        .unwrap_or(Permissions::empty());
+    let missing_permissions = access_rule.required_permissions - member_permissions;
+    if !missing_permissions.is_empty() {
+        return Err(AccessDenial::MissingPermissions(missing_permissions));
+    }
+
+    if !access_rule.allowed_role_ids.is_empty() {
+        let has_allowed_role = member.is_some_and(|member| {
+            member
+                .roles
+                .iter()
+                .any(|role_id| access_rule.allowed_role_ids.contains(role_id))
+        });
+        if !has_allowed_role {
+            return Err(AccessDenial::MissingRole);
+        }
+    }
+
+    Ok(())
+}
+
+fn find_access_rule(
+    command_name: &str,
+    guild_id: Option<GuildId>,
+) -> Option<&'static CommandAccessRule> {
+    let command_rules = config::command_access_rules()
+        .iter()
+        .filter(|access_rule| access_rule.command_name == command_name);
+
+    command_rules
+        .clone()
+        .find(|access_rule| access_rule.guild_id.is_some() && access_rule.guild_id == guild_id)
+        .or_else(|| {
+            command_rules
+                .clone()
+                .find(|access_rule| access_rule.guild_id.is_none())
+        })
+}
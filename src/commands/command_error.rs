@@ -0,0 +1,99 @@
+use serenity::all::{CommandInteraction, Context};
+use std::fmt;
+use tracing::{error, info};
+
+use crate::commands::serenity_command_helper;
+
+pub enum CommandError {
+    UserInput(String),
+    NotAllowed(String),
+    RateLimited(String),
+    NotificationDelivery(String),
+    DiscordApi(Box<serenity::Error>),
+    Internal(String),
+}
+
+impl fmt::Display for CommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CommandError::UserInput(why) => write!(f, "User input error. {}", why),
+            CommandError::NotAllowed(why) => write!(f, "Not allowed. {}", why),
+            CommandError::RateLimited(why) => write!(f, "Rate limited. {}", why),
+            CommandError::NotificationDelivery(why) => {
+                write!(f, "Notification delivery error. {}", why)
+            }
+            CommandError::DiscordApi(why) => write!(f, "Discord API error. {:?}", why),
+            CommandError::Internal(why) => write!(f, "Internal error. {}", why),
+        }
+    }
+}
+
+impl From<serenity::Error> for CommandError {
+    fn from(why: serenity::Error) -> Self {
+        CommandError::DiscordApi(Box::new(why))
+    }
+}
+
+impl CommandError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            CommandError::UserInput(_) => "user_input",
+            CommandError::NotAllowed(_) => "not_allowed",
+            CommandError::RateLimited(_) => "rate_limited",
+            CommandError::NotificationDelivery(_) => "notification_delivery",
+            CommandError::DiscordApi(_) => "discord_api",
+            CommandError::Internal(_) => "internal",
+        }
+    }
+
+    fn user_message(&self) -> Option<&str> {
+        match self {
+            CommandError::UserInput(why) | CommandError::RateLimited(why) => Some(why.as_str()),
+            CommandError::NotAllowed(_) => None,
+            CommandError::NotificationDelivery(_) => {
+                Some("The notification could not be sent, try again later")
+            }
+            CommandError::DiscordApi(_) => None,
+            CommandError::Internal(_) => Some("Something went wrong"),
+        }
+    }
+}
+
+pub async fn report_command_error(
+    ctx: &Context,
+    command: &CommandInteraction,
+    command_error: &CommandError,
+) {
+    match command_error {
+        CommandError::UserInput(_) | CommandError::RateLimited(_) => {
+            info!(error = %command_error, "Discarded application command")
+        }
+        CommandError::NotAllowed(_) => info!(
+            error = %command_error,
+            "Discarded application command, access is not allowed"
+        ),
+        _ => error!(
+            error = %command_error,
+            options = ?command.data.options,
+            "Failure running command"
+        ),
+    }
+
+    let respond_result = match (command_error, command_error.user_message()) {
+        (CommandError::NotAllowed(_), _) => {
+            serenity_command_helper::respond_not_allowed_interaction(ctx, command).await
+        }
+        (_, Some(user_message)) => {
+            serenity_command_helper::respond_interaction_with_ephemeral_string(
+                ctx,
+                command,
+                user_message,
+            )
+            .await
+        }
+        (_, None) => return,
+    };
+    if let Err(why) = respond_result {
+        error!(trace = %why, "Could not report command error");
+    }
+}
@@ -0,0 +1,108 @@
+use serenity::all::{ChannelId, CommandDataOption, CommandDataOptionValue};
+
+use crate::commands::command_error::CommandError;
+
+pub struct CommandOptions<'a> {
+    options: &'a [CommandDataOption],
+}
+
+impl<'a> CommandOptions<'a> {
+    pub fn new(options: &'a [CommandDataOption]) -> Self {
+        CommandOptions { options }
+    }
+
+    pub fn sub_command(&self) -> Result<(&'a str, CommandOptions<'a>), CommandError> {
+        let Some(sub_command) = self.options.first() else {
+            return Err(CommandError::Internal("Missing sub command".to_string()));
+        };
+
+        match &sub_command.value {
+            CommandDataOptionValue::SubCommand(sub_options) => {
+                Ok((sub_command.name.as_str(), CommandOptions::new(sub_options)))
+            }
+            _ => Err(CommandError::Internal(format!(
+                "Option is not a sub command: {}",
+                sub_command.name
+            ))),
+        }
+    }
+
+    pub fn sub_command_group(&self) -> Result<(&'a str, CommandOptions<'a>), CommandError> {
+        let Some(sub_command_group) = self.options.first() else {
+            return Err(CommandError::Internal(
+                "Missing sub command group".to_string(),
+            ));
+        };
+
+        match &sub_command_group.value {
+            CommandDataOptionValue::SubCommandGroup(group_options) => Ok((
+                sub_command_group.name.as_str(),
+                CommandOptions::new(group_options),
+            )),
+            _ => Err(CommandError::Internal(format!(
+                "Option is not a sub command group: {}",
+                sub_command_group.name
+            ))),
+        }
+    }
+
+    pub fn string(&self, name: &str) -> Result<Option<&'a str>, CommandError> {
+        match self.find_value(name) {
+            Some(CommandDataOptionValue::String(value)) => Ok(Some(value.as_str())),
+            Some(_) => Err(wrong_type_error(name, "string")),
+            None => Ok(None),
+        }
+    }
+
+    pub fn required_string(&self, name: &str) -> Result<&'a str, CommandError> {
+        self.string(name)?.ok_or_else(|| missing_error(name))
+    }
+
+    pub fn integer(&self, name: &str) -> Result<Option<i64>, CommandError> {
+        match self.find_value(name) {
+            Some(CommandDataOptionValue::Integer(value)) => Ok(Some(*value)),
+            Some(_) => Err(wrong_type_error(name, "integer")),
+            None => Ok(None),
+        }
+    }
+
+    pub fn unsigned<T: TryFrom<i64>>(&self, name: &str) -> Result<Option<T>, CommandError> {
+        self.integer(name)?
+            .map(|value| {
+                T::try_from(value)
+                    .map_err(|_| CommandError::UserInput(format!("Invalid {}: {}", name, value)))
+            })
+            .transpose()
+    }
+
+    pub fn required_unsigned<T: TryFrom<i64>>(&self, name: &str) -> Result<T, CommandError> {
+        self.unsigned(name)?.ok_or_else(|| missing_error(name))
+    }
+
+    pub fn channel(&self, name: &str) -> Result<Option<ChannelId>, CommandError> {
+        match self.find_value(name) {
+            Some(CommandDataOptionValue::Channel(channel_id)) => Ok(Some(*channel_id)),
+            Some(_) => Err(wrong_type_error(name, "channel")),
+            None => Ok(None),
+        }
+    }
+
+    pub fn required_channel(&self, name: &str) -> Result<ChannelId, CommandError> {
+        self.channel(name)?.ok_or_else(|| missing_error(name))
+    }
+
+    fn find_value(&self, name: &str) -> Option<&'a CommandDataOptionValue> {
+        self.options
+            .iter()
+            .find(|option| option.name == name)
+            .map(|option| &option.value)
+    }
+}
+
+fn missing_error(name: &str) -> CommandError {
+    CommandError::UserInput(format!("Missing {}", name))
+}
+
+fn wrong_type_error(name: &str, expected_type: &str) -> CommandError {
+    CommandError::Internal(format!("Option {} is not of type {}", name, expected_type))
+}
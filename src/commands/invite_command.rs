@@ -1,15 +1,34 @@
 use serenity::all::{CommandInteraction, Context, CreateCommand};
 
-use crate::commands::serenity_command_helper;
+use crate::commands::command_error::CommandError;
+use crate::commands::serenity_command_helper::respond_success_interaction;
+use crate::commands::slash_command::SlashCommand;
 use crate::config;
 
-pub const COMMAND_NAME: &str = "invite";
+const COMMAND_NAME: &str = "invite";
 
-pub fn register() -> CreateCommand {
+pub struct InviteCommand;
+
+#[serenity::async_trait]
+impl SlashCommand for InviteCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register()
+    }
+
+    async fn run(&self, ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError> {
+        run(ctx, command).await
+    }
+}
+
+fn register() -> CreateCommand {
     CreateCommand::new(COMMAND_NAME).description("Display an invite link to a telegram group")
 }
 
-pub async fn run(ctx: &Context, command: &CommandInteraction) -> Result<(), ()> {
+async fn run(ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError> {
     respond_success_interaction(
         ctx,
         command,
@@ -21,11 +40,3 @@ pub async fn run(ctx: &Context, command: &CommandInteraction) -> Result<(), ()>
     )
     .await
 }
-
-async fn respond_success_interaction(
-    ctx: &Context,
-    command: &CommandInteraction,
-    success_message: &str,
-) -> Result<(), ()> {
-    serenity_command_helper::respond_interaction_with_string(ctx, command, success_message).await
-}
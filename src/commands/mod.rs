@@ -1,4 +1,9 @@
 pub mod animations_command;
+pub mod command_access;
+pub mod command_error;
+pub mod command_options;
 pub mod invite_command;
+pub mod notifications_command;
 pub mod notify_command;
 pub mod serenity_command_helper;
+pub mod slash_command;
@@ -0,0 +1,306 @@
+use serenity::all::{
+    ChannelId, ChannelType, CommandInteraction, CommandOptionType, Context, CreateCommand,
+    CreateCommandOption, GuildId, Permissions,
+};
+use std::collections::BTreeMap;
+use tracing::info;
+
+use crate::commands::command_error::CommandError;
+use crate::commands::command_options::CommandOptions;
+use crate::commands::serenity_command_helper;
+use crate::commands::serenity_command_helper::respond_success_interaction;
+use crate::commands::slash_command::SlashCommand;
+use crate::notification_filter;
+use crate::notification_filter::{GuildNotificationFilter, NotificationRule};
+
+const COMMAND_NAME: &str = "notifications";
+
+const CONFIG_SUB_COMMAND_GROUP_NAME: &str = "config";
+
+const CHANNEL_SUB_COMMAND_NAME: &str = "channel";
+const AFK_SUB_COMMAND_NAME: &str = "afk";
+const DEFAULT_SUB_COMMAND_NAME: &str = "default";
+const SHOW_SUB_COMMAND_NAME: &str = "show";
+
+const RESET_RULE_NAME: &str = "reset";
+
+pub struct NotificationsCommand;
+
+#[serenity::async_trait]
+impl SlashCommand for NotificationsCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register()
+    }
+
+    async fn run(&self, ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError> {
+        run(ctx, command).await
+    }
+}
+
+fn register() -> CreateCommand {
+    CreateCommand::new(COMMAND_NAME)
+        .description("Choose which voice channels of this server notify telegram")
+        .default_member_permissions(Permissions::MANAGE_GUILD)
+        .dm_permission(false)
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::SubCommandGroup,
+                CONFIG_SUB_COMMAND_GROUP_NAME,
+                "Include or exclude voice channels from notifications",
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    CHANNEL_SUB_COMMAND_NAME,
+                    "Set the rule of a voice channel or of every channel in a category",
+                )
+                .add_sub_option(
+                    CreateCommandOption::new(
+                        CommandOptionType::Channel,
+                        "channel",
+                        "Voice channel or category",
+                    )
+                    .channel_types(vec![
+                        ChannelType::Voice,
+                        ChannelType::Stage,
+                        ChannelType::Category,
+                    ])
+                    .required(true),
+                )
+                .add_sub_option(build_rule_option(true)),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    AFK_SUB_COMMAND_NAME,
+                    "Set the rule of the server AFK channel",
+                )
+                .add_sub_option(build_rule_option(true)),
+            )
+            .add_sub_option(
+                CreateCommandOption::new(
+                    CommandOptionType::SubCommand,
+                    DEFAULT_SUB_COMMAND_NAME,
+                    "Set the rule of channels without a rule of their own",
+                )
+                .add_sub_option(build_rule_option(false)),
+            )
+            .add_sub_option(CreateCommandOption::new(
+                CommandOptionType::SubCommand,
+                SHOW_SUB_COMMAND_NAME,
+                "Show the rules of this server",
+            )),
+        )
+}
+
+fn build_rule_option(can_reset: bool) -> CreateCommandOption {
+    let rule_option = CreateCommandOption::new(
+        CommandOptionType::String,
+        "rule",
+        "Whether the channels notify",
+    )
+    .add_string_choice("Include", NotificationRule::Include.to_string())
+    .add_string_choice("Exclude", NotificationRule::Exclude.to_string())
+    .required(true);
+
+    if can_reset {
+        rule_option.add_string_choice("Reset", RESET_RULE_NAME)
+    } else {
+        rule_option
+    }
+}
+
+async fn run(ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError> {
+    let Some(guild_id) = command.guild_id else {
+        return Err(CommandError::UserInput(
+            "Notifications can only be configured in a server".to_string(),
+        ));
+    };
+
+    if !can_configure_notifications(command) {
+        return Err(CommandError::NotAllowed(
+            "Configuring notifications requires Manage Server".to_string(),
+        ));
+    }
+
+    let (sub_command_group_name, group_options) =
+        CommandOptions::new(&command.data.options).sub_command_group()?;
+    if sub_command_group_name != CONFIG_SUB_COMMAND_GROUP_NAME {
+        return Err(CommandError::Internal(format!(
+            "Unknown sub command group: {}",
+            sub_command_group_name
+        )));
+    }
+
+    let (sub_command_name, sub_options) = group_options.sub_command()?;
+    match sub_command_name {
+        CHANNEL_SUB_COMMAND_NAME => run_config_channel(ctx, command, guild_id, &sub_options).await,
+        AFK_SUB_COMMAND_NAME => run_config_afk(ctx, command, guild_id, &sub_options).await,
+        DEFAULT_SUB_COMMAND_NAME => run_config_default(ctx, command, guild_id, &sub_options).await,
+        SHOW_SUB_COMMAND_NAME => run_config_show(ctx, command, guild_id).await,
+        _ => Err(CommandError::Internal(format!(
+            "Unknown sub command: {}",
+            sub_command_name
+        ))),
+    }
+}
+
+fn can_configure_notifications(command: &CommandInteraction) -> bool {
+    command
+        .member
+        .as_ref()
+        .and_then(|member| member.permissions)
+        .is_some_and(|permissions| permissions.manage_guild())
+}
+
+async fn run_config_channel(
+    ctx: &Context,
+    command: &CommandInteraction,
+    guild_id: GuildId,
+    sub_options: &CommandOptions<'_>,
+) -> Result<(), CommandError> {
+    let channel_id = sub_options.required_channel("channel")?;
+    let rule = parse_rule(sub_options.required_string("rule")?)?;
+    let is_category = command
+        .data
+        .resolved
+        .channels
+        .get(&channel_id)
+        .is_some_and(|partial_channel| partial_channel.kind == ChannelType::Category);
+
+    notification_filter::update_guild_notification_filter(guild_id, |guild_notification_filter| {
+        let rules = if is_category {
+            &mut guild_notification_filter.category_rules
+        } else {
+            &mut guild_notification_filter.channel_rules
+        };
+        match rule {
+            Some(rule) => rules.insert(channel_id, rule),
+            None => rules.remove(&channel_id),
+        };
+    })
+    .map_err(CommandError::Internal)?;
+
+    let target = if is_category {
+        format!("category <#{}>", channel_id)
+    } else {
+        format!("<#{}>", channel_id)
+    };
+    info!(%channel_id, is_category, rule = rule_name(rule), "Set notification rule");
+
+    respond_success_interaction(ctx, command, &build_rule_change_message(&target, rule)).await
+}
+
+async fn run_config_afk(
+    ctx: &Context,
+    command: &CommandInteraction,
+    guild_id: GuildId,
+    sub_options: &CommandOptions<'_>,
+) -> Result<(), CommandError> {
+    let rule = parse_rule(sub_options.required_string("rule")?)?;
+
+    notification_filter::update_guild_notification_filter(guild_id, |guild_notification_filter| {
+        guild_notification_filter.afk_channel_rule = rule;
+    })
+    .map_err(CommandError::Internal)?;
+
+    info!(rule = rule_name(rule), "Set AFK channel notification rule");
+
+    respond_success_interaction(
+        ctx,
+        command,
+        &build_rule_change_message("the AFK channel", rule),
+    )
+    .await
+}
+
+async fn run_config_default(
+    ctx: &Context,
+    command: &CommandInteraction,
+    guild_id: GuildId,
+    sub_options: &CommandOptions<'_>,
+) -> Result<(), CommandError> {
+    let Some(rule) = parse_rule(sub_options.required_string("rule")?)? else {
+        return Err(CommandError::UserInput(
+            "The default rule cannot be reset, use include or exclude".to_string(),
+        ));
+    };
+
+    notification_filter::update_guild_notification_filter(guild_id, |guild_notification_filter| {
+        guild_notification_filter.default_rule = rule;
+    })
+    .map_err(CommandError::Internal)?;
+
+    info!(%rule, "Set default notification rule");
+
+    respond_success_interaction(
+        ctx,
+        command,
+        format!("Default notification rule set to {}", rule).as_str(),
+    )
+    .await
+}
+
+async fn run_config_show(
+    ctx: &Context,
+    command: &CommandInteraction,
+    guild_id: GuildId,
+) -> Result<(), CommandError> {
+    serenity_command_helper::respond_interaction_with_ephemeral_string(
+        ctx,
+        command,
+        &build_filter_description(&notification_filter::get_guild_notification_filter(
+            guild_id,
+        )),
+    )
+    .await
+}
+
+fn build_rule_change_message(target: &str, rule: Option<NotificationRule>) -> String {
+    match rule {
+        Some(rule) => format!("Notification rule of {} set to {}", target, rule),
+        None => format!("Notification rule of {} removed", target),
+    }
+}
+
+fn build_filter_description(guild_notification_filter: &GuildNotificationFilter) -> String {
+    format!(
+        "Default: {}\nAFK channel: {}\nCategories: {}\nChannels: {}",
+        guild_notification_filter.default_rule,
+        guild_notification_filter
+            .afk_channel_rule
+            .map_or("no rule".to_string(), |rule| rule.to_string()),
+        build_rules_description(&guild_notification_filter.category_rules),
+        build_rules_description(&guild_notification_filter.channel_rules),
+    )
+}
+
+fn build_rules_description(rules: &BTreeMap<ChannelId, NotificationRule>) -> String {
+    if rules.is_empty() {
+        return "no rules".to_string();
+    }
+
+    rules
+        .iter()
+        .map(|(channel_id, rule)| format!("<#{}> {}", channel_id, rule))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn parse_rule(rule_name: &str) -> Result<Option<NotificationRule>, CommandError> {
+    if rule_name == RESET_RULE_NAME {
+        return Ok(None);
+    }
+
+    NotificationRule::from_name(rule_name)
+        .map(Some)
+        .ok_or_else(|| CommandError::UserInput(format!("Invalid rule: {}", rule_name)))
+}
+
+fn rule_name(rule: Option<NotificationRule>) -> String {
+    rule.map_or(RESET_RULE_NAME.to_string(), |rule| rule.to_string())
+}
@@ -1,104 +1,231 @@
-use serenity::all::CreateCommandOption;
+use serenity::all::{
+    AutocompleteChoice, CreateAutocompleteResponse, CreateCommandOption, CreateInteractionResponse,
+};
 use serenity::builder::CreateCommand;
 use serenity::client::Context;
+use serenity::model::application::CommandInteraction;
 use serenity::model::application::CommandOptionType;
-use serenity::model::application::{CommandDataOption, CommandDataOptionValue, CommandInteraction};
+use tracing::info;
 
+use crate::animation::Animation;
+use crate::commands::command_error::CommandError;
+use crate::commands::command_options::CommandOptions;
 use crate::commands::serenity_command_helper;
-use crate::{animation, message_helper, serenity_model_helper, telegram};
+use crate::commands::serenity_command_helper::respond_success_interaction;
+use crate::commands::slash_command::SlashCommand;
+use crate::notification::Notification;
+use crate::{animation, config, message_helper, notification, rate_limiter, serenity_model_helper};
 
-pub const COMMAND_NAME: &str = "notify";
+const COMMAND_NAME: &str = "notify";
 
-pub fn register() -> CreateCommand {
+const INDEX_OPTION_NAME: &str = "index";
+const MESSAGE_OPTION_NAME: &str = "message";
+const ANIMATION_OPTION_NAME: &str = "animation";
+
+const MAX_AUTOCOMPLETE_CHOICES: usize = 25;
+const MAX_AUTOCOMPLETE_CHOICE_NAME_LENGTH: usize = 100;
+
+pub struct NotifyCommand;
+
+#[serenity::async_trait]
+impl SlashCommand for NotifyCommand {
+    fn name(&self) -> &'static str {
+        COMMAND_NAME
+    }
+
+    fn register(&self) -> CreateCommand {
+        register()
+    }
+
+    async fn run(&self, ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError> {
+        run(ctx, command).await
+    }
+
+    async fn autocomplete(
+        &self,
+        ctx: &Context,
+        command: &CommandInteraction,
+    ) -> Result<(), CommandError> {
+        autocomplete(ctx, command).await
+    }
+}
+
+fn register() -> CreateCommand {
     CreateCommand::new(COMMAND_NAME)
         .description("Notify everyone via text channel")
         .add_option(
             CreateCommandOption::new(
                 CommandOptionType::Integer,
-                "index",
+                INDEX_OPTION_NAME,
                 "Index of the chosen media to send",
             )
             .min_int_value(0)
+            .set_autocomplete(true)
             .required(false),
         )
         .add_option(
             CreateCommandOption::new(
                 CommandOptionType::String,
-                "message",
+                MESSAGE_OPTION_NAME,
                 "Custom message to send",
             )
             .required(false),
         )
+        .add_option(
+            CreateCommandOption::new(
+                CommandOptionType::String,
+                ANIMATION_OPTION_NAME,
+                "Name of the chosen media to send",
+            )
+            .set_autocomplete(true)
+            .required(false),
+        )
 }
 
-pub async fn run(ctx: &Context, command: &CommandInteraction) -> Result<(), ()> {
-    let options: &[CommandDataOption] = command.data.options.as_slice();
+async fn run(ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError> {
+    let options = CommandOptions::new(&command.data.options);
 
-    let option_animation_index: Option<&CommandDataOptionValue> =
-        options.get(0).map(|option| &option.value);
+    let option_animation_index: Option<usize> = options.unsigned(INDEX_OPTION_NAME)?;
 
-    let option_message: Option<&CommandDataOptionValue> =
-        options.get(1).map(|option| &option.value);
+    let option_animation_name: Option<&str> = options.string(ANIMATION_OPTION_NAME)?;
 
-    let animation_url: &str =
-        if let Some(CommandDataOptionValue::Integer(inputted_animation_index)) =
-            option_animation_index
-        {
-            let index = usize::try_from(*inputted_animation_index).unwrap();
-            if let Some(some_animation_url) = animation::get_animation_url(index) {
-                &some_animation_url.to_owned()
-            } else {
-                let animation_urls_size = animation::get_animation_urls_size();
-                return respond_fail_interaction(
-                    ctx,
-                    command,
-                    format!(
-                        "Index {} does not exist. Use a value between {} and {}",
-                        index,
-                        0,
-                        animation_urls_size - 1
-                    )
-                    .as_str(),
-                )
-                .await;
+    let option_message: Option<&str> = options.string(MESSAGE_OPTION_NAME)?;
+
+    let animation_url: &str = if let Some(index) = option_animation_index {
+        match animation::get_animation(index) {
+            Some(some_animation) if some_animation.enabled => &some_animation.url.to_owned(),
+            Some(some_animation) => {
+                return Err(CommandError::UserInput(format!(
+                    "Animation {} is disabled",
+                    some_animation.name
+                )));
+            }
+            None => {
+                let animations_size = animation::get_animations_size();
+                return Err(CommandError::UserInput(format!(
+                    "Index {} does not exist. Use a value between {} and {}",
+                    index,
+                    0,
+                    animations_size - 1
+                )));
+            }
+        }
+    } else if let Some(inputted_animation_name) = option_animation_name {
+        match animation::find_animation(inputted_animation_name) {
+            Some((_, some_animation)) if some_animation.enabled => &some_animation.url.to_owned(),
+            Some((_, some_animation)) => {
+                return Err(CommandError::UserInput(format!(
+                    "Animation {} is disabled",
+                    some_animation.name
+                )));
+            }
+            None => {
+                return Err(CommandError::UserInput(format!(
+                    "Animation {} does not exist",
+                    inputted_animation_name
+                )));
             }
-        } else {
-            &animation::get_random_animation_url()
-        };
-
-    let message: &str = if let Some(CommandDataOptionValue::String(inputted_message)) =
-        option_message
-    {
-        inputted_message.as_str()
+        }
+    } else {
+        &animation::get_random_animation_url()
+    };
+
+    let message: &str = if let Some(inputted_message) = option_message {
+        inputted_message
     } else {
         let user_name: &str = command.user.name.as_str();
         let channel_name: &str =
-            &serenity_model_helper::get_channel_name_from_application_command(ctx, command)
-                .await;
+            &serenity_model_helper::get_channel_name_from_application_command(ctx, command).await;
         let guild_name: &str =
-            &serenity_model_helper::get_guild_name_from_application_command(ctx, command)
-                .await;
+            &serenity_model_helper::get_guild_name_from_application_command(ctx, command).await;
 
-        &message_helper::build_text_channel_notification_message(user_name, channel_name, guild_name)
+        &message_helper::build_text_channel_notification_message(
+            user_name,
+            channel_name,
+            guild_name,
+        )
     };
 
-    telegram::send_notification_to_telegram(animation_url, message);
+    if !is_rate_limit_exempt(command) {
+        if let Err(rejection) =
+            rate_limiter::try_acquire_notify_command(command.user.id, command.guild_id)
+        {
+            info!(
+                reason = "rate_limit",
+                scope = %rejection.scope,
+                retry_after_seconds = rejection.retry_after.as_secs(),
+                rejected_count = rate_limiter::notify_command_rejected_count(),
+                "Discarded notify command"
+            );
+            return Err(CommandError::RateLimited(format!(
+                "Too many notifications, try again in {} seconds",
+                rejection.retry_after.as_secs().max(1)
+            )));
+        }
+    }
+
+    notification::dispatch_notification(&Notification::new(
+        command.guild_id,
+        Some(command.channel_id),
+        animation_url,
+        message,
+    ))
+    .await
+    .map_err(CommandError::NotificationDelivery)?;
 
     respond_success_interaction(ctx, command, "Success!").await
 }
 
-async fn respond_success_interaction(
-    ctx: &Context,
-    command: &CommandInteraction,
-    success_message: &str,
-) -> Result<(), ()> {
-    serenity_command_helper::respond_interaction_with_string(ctx, command, success_message).await
+async fn autocomplete(ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError> {
+    let Some(autocomplete_option) = command.data.autocomplete() else {
+        return Ok(());
+    };
+
+    let matching_animations =
+        animation::search_animations(autocomplete_option.value, MAX_AUTOCOMPLETE_CHOICES);
+
+    let choices: Vec<AutocompleteChoice> = matching_animations
+        .into_iter()
+        .map(|(index, animation)| {
+            let choice_name = build_autocomplete_choice_name(index, &animation);
+            match autocomplete_option.name {
+                INDEX_OPTION_NAME => AutocompleteChoice::new(choice_name, index as i64),
+                _ => AutocompleteChoice::new(choice_name, animation.id),
+            }
+        })
+        .collect();
+
+    serenity_command_helper::respond_interaction(ctx, command, || {
+        CreateInteractionResponse::Autocomplete(
+            CreateAutocompleteResponse::new().set_choices(choices.clone()),
+        )
+    })
+    .await
+}
+
+fn build_autocomplete_choice_name(index: usize, animation: &Animation) -> String {
+    let choice_name = if animation.tags.is_empty() {
+        format!("{}. {}", index, animation.name)
+    } else {
+        format!(
+            "{}. {} [{}]",
+            index,
+            animation.name,
+            animation.tags.join(", ")
+        )
+    };
+
+    choice_name
+        .chars()
+        .take(MAX_AUTOCOMPLETE_CHOICE_NAME_LENGTH)
+        .collect()
 }
 
-async fn respond_fail_interaction(
-    ctx: &Context,
-    command: &CommandInteraction,
-    error_message: &str,
-) -> Result<(), ()> {
-    serenity_command_helper::respond_interaction_with_string(ctx, command, error_message).await
+fn is_rate_limit_exempt(command: &CommandInteraction) -> bool {
+    command.member.as_ref().is_some_and(|member| {
+        member
+            .roles
+            .iter()
+            .any(|role_id| config::notify_rate_limit_exempt_role_ids().contains(role_id))
+    })
 }
@@ -1,20 +1,16 @@
 use serenity::all::{
-    CommandInteraction, Context, CreateInteractionResponse, CreateInteractionResponseMessage,
+    CommandInteraction, ComponentInteraction, Context, CreateInteractionResponse,
+    CreateInteractionResponseMessage,
 };
 
+use crate::commands::command_error::CommandError;
+
 pub async fn respond_interaction(
     ctx: &Context,
     command: &CommandInteraction,
     f: impl Fn() -> CreateInteractionResponse,
-) -> Result<(), ()> {
-    if let Err(why) = command.create_response(ctx, f()).await {
-        println!(
-            "Error. Cannot respond to slash command. CommandName: {}. Trace: {:?}",
-            command.data.name, why
-        );
-
-        return Err(());
-    }
+) -> Result<(), CommandError> {
+    command.create_response(ctx, f()).await?;
 
     Ok(())
 }
@@ -23,7 +19,7 @@ pub async fn respond_interaction_with_string(
     ctx: &Context,
     command: &CommandInteraction,
     response_message: &str,
-) -> Result<(), ()> {
+) -> Result<(), CommandError> {
     respond_interaction(ctx, command, || {
         CreateInteractionResponse::Message(
             CreateInteractionResponseMessage::new().content(response_message),
@@ -31,3 +27,75 @@ pub async fn respond_interaction_with_string(
     })
     .await
 }
+
+pub async fn respond_success_interaction(
+    ctx: &Context,
+    command: &CommandInteraction,
+    success_message: &str,
+) -> Result<(), CommandError> {
+    respond_interaction_with_string(ctx, command, success_message).await
+}
+
+pub async fn respond_interaction_with_ephemeral_string(
+    ctx: &Context,
+    command: &CommandInteraction,
+    response_message: &str,
+) -> Result<(), CommandError> {
+    respond_interaction(ctx, command, || {
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(response_message)
+                .ephemeral(true),
+        )
+    })
+    .await
+}
+
+pub async fn respond_not_allowed_interaction(
+    ctx: &Context,
+    command: &CommandInteraction,
+) -> Result<(), CommandError> {
+    respond_interaction_with_ephemeral_string(
+        ctx,
+        command,
+        "You are not allowed to use this command",
+    )
+    .await
+}
+
+pub async fn respond_component_interaction(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    f: impl Fn() -> CreateInteractionResponse,
+) -> Result<(), CommandError> {
+    component.create_response(ctx, f()).await?;
+
+    Ok(())
+}
+
+pub async fn respond_not_allowed_component_interaction(
+    ctx: &Context,
+    component: &ComponentInteraction,
+) -> Result<(), CommandError> {
+    respond_component_interaction_with_ephemeral_string(
+        ctx,
+        component,
+        "You are not allowed to use this command",
+    )
+    .await
+}
+
+pub async fn respond_component_interaction_with_ephemeral_string(
+    ctx: &Context,
+    component: &ComponentInteraction,
+    response_message: &str,
+) -> Result<(), CommandError> {
+    respond_component_interaction(ctx, component, || {
+        CreateInteractionResponse::Message(
+            CreateInteractionResponseMessage::new()
+                .content(response_message)
+                .ephemeral(true),
+        )
+    })
+    .await
+}
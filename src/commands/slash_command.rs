@@ -0,0 +1,55 @@
+use serenity::all::{CommandInteraction, Context, CreateCommand};
+use std::sync::LazyLock;
+
+use crate::commands::animations_command::AnimationsCommand;
+use crate::commands::command_error::CommandError;
+use crate::commands::invite_command::InviteCommand;
+use crate::commands::notifications_command::NotificationsCommand;
+use crate::commands::notify_command::NotifyCommand;
+
+static SLASH_COMMANDS: LazyLock<Vec<Box<dyn SlashCommand>>> = LazyLock::new(|| {
+    vec![
+        Box::new(NotifyCommand),
+        Box::new(AnimationsCommand),
+        Box::new(InviteCommand),
+        Box::new(NotificationsCommand),
+    ]
+});
+
+#[serenity::async_trait]
+pub trait SlashCommand: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    fn register(&self) -> CreateCommand;
+
+    async fn run(&self, ctx: &Context, command: &CommandInteraction) -> Result<(), CommandError>;
+
+    async fn autocomplete(
+        &self,
+        _ctx: &Context,
+        _command: &CommandInteraction,
+    ) -> Result<(), CommandError> {
+        Ok(())
+    }
+}
+
+pub fn register_slash_commands() -> Vec<CreateCommand> {
+    SLASH_COMMANDS
+        .iter()
+        .map(|slash_command| slash_command.register())
+        .collect()
+}
+
+pub fn slash_command_names() -> Vec<&'static str> {
+    SLASH_COMMANDS
+        .iter()
+        .map(|slash_command| slash_command.name())
+        .collect()
+}
+
+pub fn find_slash_command(name: &str) -> Option<&'static dyn SlashCommand> {
+    SLASH_COMMANDS
+        .iter()
+        .find(|slash_command| slash_command.name() == name)
+        .map(|slash_command| slash_command.as_ref())
+}
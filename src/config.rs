@@ -1,45 +1,1227 @@
+use serde::Deserialize;
+use serenity::all::{ChannelId, GuildId, Permissions, RoleId, UserId};
 use std::env;
-use std::ops::Deref;
-use std::string::ToString;
-use std::sync::LazyLock;
+use std::fs;
+use std::net::SocketAddr;
+use std::num::NonZeroU64;
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tracing::info;
 
+use crate::commands::slash_command;
+use crate::message_helper;
+use crate::rate_limiter::TokenBucketLimit;
+use crate::voice_transition::VoiceActivity;
+
+const CONFIG_FILE_ENV: &str = "HOSEUS_BOT_CONFIG_FILE";
 const DISCORD_BOT_TOKEN_ENV: &str = "DISCORD_BOT_TOKEN";
 const TELEGRAM_BOT_TOKEN_ENV: &str = "TELEGRAM_BOT_TOKEN";
 const TELEGRAM_CHAT_ID_ENV: &str = "TELEGRAM_CHAT_ID";
 const TELEGRAM_INVITE_LINK_ENV: &str = "TELEGRAM_INVITE_LINK";
+const TELEGRAM_CHAT_ROUTES_ENV: &str = "TELEGRAM_CHAT_ROUTES";
+const TELEGRAM_API_BASE_URL_ENV: &str = "TELEGRAM_API_BASE_URL";
+const TELEGRAM_CONNECT_TIMEOUT_SECONDS_ENV: &str = "TELEGRAM_CONNECT_TIMEOUT_SECONDS";
+const TELEGRAM_REQUEST_TIMEOUT_SECONDS_ENV: &str = "TELEGRAM_REQUEST_TIMEOUT_SECONDS";
+const TELEGRAM_HEALTH_CHECK_INTERVAL_SECONDS_ENV: &str = "TELEGRAM_HEALTH_CHECK_INTERVAL_SECONDS";
+const NOTIFICATION_MAX_DELIVERY_ATTEMPTS_ENV: &str = "NOTIFICATION_MAX_DELIVERY_ATTEMPTS";
+const NOTIFICATION_RETRY_BASE_DELAY_SECONDS_ENV: &str = "NOTIFICATION_RETRY_BASE_DELAY_SECONDS";
+const NOTIFICATION_RETRY_MAX_DELAY_SECONDS_ENV: &str = "NOTIFICATION_RETRY_MAX_DELAY_SECONDS";
+const NOTIFICATION_OUTBOX_FILE_ENV: &str = "NOTIFICATION_OUTBOX_FILE";
+const NOTIFICATION_FILTER_FILE_ENV: &str = "NOTIFICATION_FILTER_FILE";
+const NOTIFICATION_OUTBOX_MAX_AGE_SECONDS_ENV: &str = "NOTIFICATION_OUTBOX_MAX_AGE_SECONDS";
+const NOTIFICATION_SHUTDOWN_DRAIN_TIMEOUT_SECONDS_ENV: &str =
+    "NOTIFICATION_SHUTDOWN_DRAIN_TIMEOUT_SECONDS";
+const ANIMATION_FILE_ENV: &str = "ANIMATION_FILE";
+const ANIMATION_RELOAD_INTERVAL_SECONDS_ENV: &str = "ANIMATION_RELOAD_INTERVAL_SECONDS";
+const ANIMATION_MANAGER_ROLE_IDS_ENV: &str = "ANIMATION_MANAGER_ROLE_IDS";
+const VOICE_JOIN_USER_COOLDOWN_SECONDS_ENV: &str = "VOICE_JOIN_USER_COOLDOWN_SECONDS";
+const VOICE_JOIN_CHANNEL_COOLDOWN_SECONDS_ENV: &str = "VOICE_JOIN_CHANNEL_COOLDOWN_SECONDS";
+const VOICE_JOIN_GUILD_COOLDOWN_SECONDS_ENV: &str = "VOICE_JOIN_GUILD_COOLDOWN_SECONDS";
+const VOICE_SESSION_END_ENABLED_ENV: &str = "VOICE_SESSION_END_ENABLED";
+const VOICE_SESSION_END_MESSAGE_TEMPLATE_ENV: &str = "VOICE_SESSION_END_MESSAGE_TEMPLATE";
+const VOICE_STREAM_ENABLED_ENV: &str = "VOICE_STREAM_ENABLED";
+const VOICE_STREAM_MESSAGE_TEMPLATE_ENV: &str = "VOICE_STREAM_MESSAGE_TEMPLATE";
+const VOICE_STREAM_USER_COOLDOWN_SECONDS_ENV: &str = "VOICE_STREAM_USER_COOLDOWN_SECONDS";
+const VOICE_STREAM_CHANNEL_COOLDOWN_SECONDS_ENV: &str = "VOICE_STREAM_CHANNEL_COOLDOWN_SECONDS";
+const VOICE_STREAM_GUILD_COOLDOWN_SECONDS_ENV: &str = "VOICE_STREAM_GUILD_COOLDOWN_SECONDS";
+const VOICE_VIDEO_ENABLED_ENV: &str = "VOICE_VIDEO_ENABLED";
+const VOICE_VIDEO_MESSAGE_TEMPLATE_ENV: &str = "VOICE_VIDEO_MESSAGE_TEMPLATE";
+const VOICE_VIDEO_USER_COOLDOWN_SECONDS_ENV: &str = "VOICE_VIDEO_USER_COOLDOWN_SECONDS";
+const VOICE_VIDEO_CHANNEL_COOLDOWN_SECONDS_ENV: &str = "VOICE_VIDEO_CHANNEL_COOLDOWN_SECONDS";
+const VOICE_VIDEO_GUILD_COOLDOWN_SECONDS_ENV: &str = "VOICE_VIDEO_GUILD_COOLDOWN_SECONDS";
+const NOTIFY_RATE_LIMIT_USER_CAPACITY_ENV: &str = "NOTIFY_RATE_LIMIT_USER_CAPACITY";
+const NOTIFY_RATE_LIMIT_USER_REFILL_SECONDS_ENV: &str = "NOTIFY_RATE_LIMIT_USER_REFILL_SECONDS";
+const NOTIFY_RATE_LIMIT_GUILD_CAPACITY_ENV: &str = "NOTIFY_RATE_LIMIT_GUILD_CAPACITY";
+const NOTIFY_RATE_LIMIT_GUILD_REFILL_SECONDS_ENV: &str = "NOTIFY_RATE_LIMIT_GUILD_REFILL_SECONDS";
+const NOTIFY_RATE_LIMIT_EXEMPT_ROLE_IDS_ENV: &str = "NOTIFY_RATE_LIMIT_EXEMPT_ROLE_IDS";
+const HTTP_SERVER_LISTEN_ADDRESS_ENV: &str = "HTTP_SERVER_LISTEN_ADDRESS";
+
+const DEFAULT_CONFIG_FILE: &str = "config.toml";
+const DEFAULT_TELEGRAM_API_BASE_URL: &str = "https://api.telegram.org";
+const DEFAULT_TELEGRAM_CONNECT_TIMEOUT_SECONDS: u64 = 5;
+const DEFAULT_TELEGRAM_REQUEST_TIMEOUT_SECONDS: u64 = 15;
+const DEFAULT_TELEGRAM_HEALTH_CHECK_INTERVAL_SECONDS: u64 = 60;
+const DEFAULT_NOTIFICATION_MAX_DELIVERY_ATTEMPTS: u32 = 5;
+const DEFAULT_NOTIFICATION_RETRY_BASE_DELAY_SECONDS: u64 = 2;
+const DEFAULT_NOTIFICATION_RETRY_MAX_DELAY_SECONDS: u64 = 300;
+const DEFAULT_NOTIFICATION_OUTBOX_FILE: &str = "notification_outbox.jsonl";
+const DEFAULT_NOTIFICATION_FILTER_FILE: &str = "notification_filters.json";
+const DEFAULT_NOTIFICATION_OUTBOX_MAX_AGE_SECONDS: u64 = 1800;
+const DEFAULT_NOTIFICATION_SHUTDOWN_DRAIN_TIMEOUT_SECONDS: u64 = 10;
+const DEFAULT_ANIMATION_FILE: &str = "animation_urls.json";
+const DEFAULT_ANIMATION_RELOAD_INTERVAL_SECONDS: u64 = 10;
+const DEFAULT_VOICE_JOIN_USER_COOLDOWN_SECONDS: u64 = 600;
+const DEFAULT_VOICE_JOIN_CHANNEL_COOLDOWN_SECONDS: u64 = 120;
+const DEFAULT_VOICE_JOIN_GUILD_COOLDOWN_SECONDS: u64 = 0;
+const DEFAULT_VOICE_SESSION_END_ENABLED: bool = false;
+const DEFAULT_VOICE_SESSION_END_MESSAGE_TEMPLATE: &str =
+    "Session in voice channel *{channel}* in server *{guild}* ended after {duration}";
+const DEFAULT_VOICE_ACTIVITY_ENABLED: bool = false;
+const DEFAULT_VOICE_ACTIVITY_USER_COOLDOWN_SECONDS: u64 = 600;
+const DEFAULT_VOICE_ACTIVITY_CHANNEL_COOLDOWN_SECONDS: u64 = 120;
+const DEFAULT_VOICE_ACTIVITY_GUILD_COOLDOWN_SECONDS: u64 = 0;
+const DEFAULT_VOICE_STREAM_MESSAGE_TEMPLATE: &str =
+    "*{user}* started streaming in voice channel *{channel}* in server *{guild}*. Are you watching?";
+const DEFAULT_VOICE_VIDEO_MESSAGE_TEMPLATE: &str =
+    "*{user}* turned on their camera in voice channel *{channel}* in server *{guild}*. Are you joining?";
+const DEFAULT_NOTIFY_RATE_LIMIT_USER_CAPACITY: u32 = 3;
+const DEFAULT_NOTIFY_RATE_LIMIT_USER_REFILL_SECONDS: u64 = 60;
+const DEFAULT_NOTIFY_RATE_LIMIT_GUILD_CAPACITY: u32 = 10;
+const DEFAULT_NOTIFY_RATE_LIMIT_GUILD_REFILL_SECONDS: u64 = 30;
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+struct Config {
+    discord_bot_token: String,
+    telegram: TelegramConfig,
+    notification: NotificationConfig,
+    animation: AnimationConfig,
+    voice_join: VoiceJoinConfig,
+    voice_session_end: VoiceSessionEndConfig,
+    voice_stream: VoiceActivityConfig,
+    voice_video: VoiceActivityConfig,
+    notify_rate_limit: NotifyRateLimitConfig,
+    command_access_rules: Vec<CommandAccessRule>,
+    http_server: HttpServerConfig,
+}
+
+struct TelegramConfig {
+    bot_token: String,
+    chat_id: String,
+    invite_link: String,
+    chat_routes: Vec<TelegramChatRoute>,
+    api_base_url: String,
+    connect_timeout: Duration,
+    request_timeout: Duration,
+    health_check_interval: Duration,
+}
+
+pub struct TelegramChatRoute {
+    pub guild_id: GuildId,
+    pub channel_id: Option<ChannelId>,
+    pub chat_ids: Vec<String>,
+}
+
+struct NotificationConfig {
+    max_delivery_attempts: u32,
+    retry_base_delay: Duration,
+    retry_max_delay: Duration,
+    outbox_file: String,
+    filter_file: String,
+    outbox_max_age: Duration,
+    shutdown_drain_timeout: Duration,
+}
+
+struct AnimationConfig {
+    file: String,
+    reload_interval: Duration,
+    manager_role_ids: Vec<RoleId>,
+}
+
+struct VoiceJoinConfig {
+    user_cooldown: Duration,
+    channel_cooldown: Duration,
+    guild_cooldown: Duration,
+}
+
+struct VoiceSessionEndConfig {
+    enabled: bool,
+    message_template: String,
+}
+
+pub struct VoiceActivityConfig {
+    pub enabled: bool,
+    pub message_template: String,
+    pub user_cooldown: Duration,
+    pub channel_cooldown: Duration,
+    pub guild_cooldown: Duration,
+}
+
+pub struct CommandAccessRule {
+    pub command_name: String,
+    pub guild_id: Option<GuildId>,
+    pub allowed_role_ids: Vec<RoleId>,
+    pub required_permissions: Permissions,
+    pub denied_user_ids: Vec<UserId>,
+}
 
-static DISCORD_BOT_TOKEN: LazyLock<String> = LazyLock::new(|| {
-    env::var(DISCORD_BOT_TOKEN_ENV)
-        .expect(format!("Env variable not defined: {}", DISCORD_BOT_TOKEN_ENV).as_str())
-});
+struct HttpServerConfig {
+    listen_address: Option<SocketAddr>,
+}
+
+struct NotifyRateLimitConfig {
+    user_limit: TokenBucketLimit,
+    guild_limit: TokenBucketLimit,
+    exempt_role_ids: Vec<RoleId>,
+}
 
-static TELEGRAM_BOT_TOKEN: LazyLock<String> = LazyLock::new(|| {
-    env::var(TELEGRAM_BOT_TOKEN_ENV)
-        .expect(format!("Env variable not defined: {}", TELEGRAM_BOT_TOKEN_ENV).as_str())
-});
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileConfig {
+    discord: FileDiscordConfig,
+    telegram: FileTelegramConfig,
+    notification: FileNotificationConfig,
+    animation: FileAnimationConfig,
+    voice_join: FileVoiceJoinConfig,
+    voice_session_end: FileVoiceSessionEndConfig,
+    voice_stream: FileVoiceActivityConfig,
+    voice_video: FileVoiceActivityConfig,
+    notify_rate_limit: FileNotifyRateLimitConfig,
+    command_access: Vec<FileCommandAccessRule>,
+    http_server: FileHttpServerConfig,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileDiscordConfig {
+    bot_token: Option<String>,
+}
 
-static TELEGRAM_CHAT_ID: LazyLock<String> = LazyLock::new(|| {
-    env::var(TELEGRAM_CHAT_ID_ENV)
-        .expect(format!("Env variable not defined: {}", TELEGRAM_CHAT_ID_ENV).as_str())
-});
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileTelegramConfig {
+    bot_token: Option<String>,
+    chat_id: Option<String>,
+    invite_link: Option<String>,
+    chat_routes: Option<Vec<FileTelegramChatRoute>>,
+    api_base_url: Option<String>,
+    connect_timeout_seconds: Option<u64>,
+    request_timeout_seconds: Option<u64>,
+    health_check_interval_seconds: Option<u64>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileTelegramChatRoute {
+    guild_id: u64,
+    channel_id: Option<u64>,
+    chat_ids: Vec<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileNotificationConfig {
+    max_delivery_attempts: Option<u32>,
+    retry_base_delay_seconds: Option<u64>,
+    retry_max_delay_seconds: Option<u64>,
+    outbox_file: Option<String>,
+    filter_file: Option<String>,
+    outbox_max_age_seconds: Option<u64>,
+    shutdown_drain_timeout_seconds: Option<u64>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileAnimationConfig {
+    file: Option<String>,
+    reload_interval_seconds: Option<u64>,
+    manager_role_ids: Option<Vec<u64>>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileVoiceJoinConfig {
+    user_cooldown_seconds: Option<u64>,
+    channel_cooldown_seconds: Option<u64>,
+    guild_cooldown_seconds: Option<u64>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileVoiceSessionEndConfig {
+    enabled: Option<bool>,
+    message_template: Option<String>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileVoiceActivityConfig {
+    enabled: Option<bool>,
+    message_template: Option<String>,
+    user_cooldown_seconds: Option<u64>,
+    channel_cooldown_seconds: Option<u64>,
+    guild_cooldown_seconds: Option<u64>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileNotifyRateLimitConfig {
+    user_capacity: Option<u32>,
+    user_refill_seconds: Option<u64>,
+    guild_capacity: Option<u32>,
+    guild_refill_seconds: Option<u64>,
+    exempt_role_ids: Option<Vec<u64>>,
+}
 
-static TELEGRAM_INVITE_LINK: LazyLock<String> = LazyLock::new(|| {
-    env::var(TELEGRAM_INVITE_LINK_ENV)
-        .expect(format!("Env variable not defined: {}", TELEGRAM_INVITE_LINK_ENV).as_str())
-});
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct FileHttpServerConfig {
+    listen_address: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct FileCommandAccessRule {
+    command: String,
+    guild_id: Option<u64>,
+    #[serde(default)]
+    allowed_role_ids: Vec<u64>,
+    #[serde(default)]
+    required_permissions: Vec<String>,
+    #[serde(default)]
+    denied_user_ids: Vec<u64>,
+}
+
+pub fn load_config() -> Result<(), Vec<String>> {
+    let mut errors: Vec<String> = Vec::new();
+
+    let mut file_config = read_file_config(&mut errors);
+    apply_env_overrides(&mut file_config, &mut errors);
+    let config = validate(file_config, &mut errors);
+
+    match config {
+        Some(config) if errors.is_empty() => CONFIG
+            .set(config)
+            .map_err(|_| vec!["Configuration already loaded".to_string()]),
+        _ => Err(errors),
+    }
+}
+
+fn read_file_config(errors: &mut Vec<String>) -> FileConfig {
+    let (file_path, is_explicit_path) = match env::var(CONFIG_FILE_ENV) {
+        Ok(file_path) => (file_path, true),
+        Err(_) => (DEFAULT_CONFIG_FILE.to_string(), false),
+    };
+
+    let toml_string = match fs::read_to_string(&file_path) {
+        Ok(toml_string) => toml_string,
+        Err(why) => {
+            if is_explicit_path {
+                errors.push(format!(
+                    "Unable to read config file {}. Trace: {:?}",
+                    file_path, why
+                ));
+            } else {
+                info!(file_path, "Config file not found, using env variables only");
+            }
+            return FileConfig::default();
+        }
+    };
+
+    toml::from_str(&toml_string).unwrap_or_else(|why| {
+        errors.push(format!(
+            "Unable to parse config file {}. Trace: {}",
+            file_path, why
+        ));
+        FileConfig::default()
+    })
+}
+
+fn apply_env_overrides(file_config: &mut FileConfig, errors: &mut Vec<String>) {
+    let discord = &mut file_config.discord;
+    env_override(&mut discord.bot_token, DISCORD_BOT_TOKEN_ENV, errors);
+
+    let telegram = &mut file_config.telegram;
+    env_override(&mut telegram.bot_token, TELEGRAM_BOT_TOKEN_ENV, errors);
+    env_override(&mut telegram.chat_id, TELEGRAM_CHAT_ID_ENV, errors);
+    env_override(&mut telegram.invite_link, TELEGRAM_INVITE_LINK_ENV, errors);
+    env_override(
+        &mut telegram.api_base_url,
+        TELEGRAM_API_BASE_URL_ENV,
+        errors,
+    );
+    env_override(
+        &mut telegram.connect_timeout_seconds,
+        TELEGRAM_CONNECT_TIMEOUT_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut telegram.request_timeout_seconds,
+        TELEGRAM_REQUEST_TIMEOUT_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut telegram.health_check_interval_seconds,
+        TELEGRAM_HEALTH_CHECK_INTERVAL_SECONDS_ENV,
+        errors,
+    );
+    if let Ok(value) = env::var(TELEGRAM_CHAT_ROUTES_ENV) {
+        match parse_telegram_chat_routes(&value) {
+            Ok(chat_routes) => telegram.chat_routes = Some(chat_routes),
+            Err(why) => errors.push(format!(
+                "Env variable has an invalid value: {}. {}",
+                TELEGRAM_CHAT_ROUTES_ENV, why
+            )),
+        }
+    }
+
+    let notification = &mut file_config.notification;
+    env_override(
+        &mut notification.max_delivery_attempts,
+        NOTIFICATION_MAX_DELIVERY_ATTEMPTS_ENV,
+        errors,
+    );
+    env_override(
+        &mut notification.retry_base_delay_seconds,
+        NOTIFICATION_RETRY_BASE_DELAY_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut notification.retry_max_delay_seconds,
+        NOTIFICATION_RETRY_MAX_DELAY_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut notification.outbox_file,
+        NOTIFICATION_OUTBOX_FILE_ENV,
+        errors,
+    );
+    env_override(
+        &mut notification.filter_file,
+        NOTIFICATION_FILTER_FILE_ENV,
+        errors,
+    );
+    env_override(
+        &mut notification.outbox_max_age_seconds,
+        NOTIFICATION_OUTBOX_MAX_AGE_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut notification.shutdown_drain_timeout_seconds,
+        NOTIFICATION_SHUTDOWN_DRAIN_TIMEOUT_SECONDS_ENV,
+        errors,
+    );
+
+    let animation = &mut file_config.animation;
+    env_override(&mut animation.file, ANIMATION_FILE_ENV, errors);
+    env_override(
+        &mut animation.reload_interval_seconds,
+        ANIMATION_RELOAD_INTERVAL_SECONDS_ENV,
+        errors,
+    );
+    env_override_ids(
+        &mut animation.manager_role_ids,
+        ANIMATION_MANAGER_ROLE_IDS_ENV,
+        errors,
+    );
+
+    let voice_join = &mut file_config.voice_join;
+    env_override(
+        &mut voice_join.user_cooldown_seconds,
+        VOICE_JOIN_USER_COOLDOWN_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut voice_join.channel_cooldown_seconds,
+        VOICE_JOIN_CHANNEL_COOLDOWN_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut voice_join.guild_cooldown_seconds,
+        VOICE_JOIN_GUILD_COOLDOWN_SECONDS_ENV,
+        errors,
+    );
+
+    let voice_session_end = &mut file_config.voice_session_end;
+    env_override(
+        &mut voice_session_end.enabled,
+        VOICE_SESSION_END_ENABLED_ENV,
+        errors,
+    );
+    env_override(
+        &mut voice_session_end.message_template,
+        VOICE_SESSION_END_MESSAGE_TEMPLATE_ENV,
+        errors,
+    );
+
+    let voice_stream = &mut file_config.voice_stream;
+    env_override(&mut voice_stream.enabled, VOICE_STREAM_ENABLED_ENV, errors);
+    env_override(
+        &mut voice_stream.message_template,
+        VOICE_STREAM_MESSAGE_TEMPLATE_ENV,
+        errors,
+    );
+    env_override(
+        &mut voice_stream.user_cooldown_seconds,
+        VOICE_STREAM_USER_COOLDOWN_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut voice_stream.channel_cooldown_seconds,
+        VOICE_STREAM_CHANNEL_COOLDOWN_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut voice_stream.guild_cooldown_seconds,
+        VOICE_STREAM_GUILD_COOLDOWN_SECONDS_ENV,
+        errors,
+    );
+
+    let voice_video = &mut file_config.voice_video;
+    env_override(&mut voice_video.enabled, VOICE_VIDEO_ENABLED_ENV, errors);
+    env_override(
+        &mut voice_video.message_template,
+        VOICE_VIDEO_MESSAGE_TEMPLATE_ENV,
+        errors,
+    );
+    env_override(
+        &mut voice_video.user_cooldown_seconds,
+        VOICE_VIDEO_USER_COOLDOWN_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut voice_video.channel_cooldown_seconds,
+        VOICE_VIDEO_CHANNEL_COOLDOWN_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut voice_video.guild_cooldown_seconds,
+        VOICE_VIDEO_GUILD_COOLDOWN_SECONDS_ENV,
+        errors,
+    );
+
+    let notify_rate_limit = &mut file_config.notify_rate_limit;
+    env_override(
+        &mut notify_rate_limit.user_capacity,
+        NOTIFY_RATE_LIMIT_USER_CAPACITY_ENV,
+        errors,
+    );
+    env_override(
+        &mut notify_rate_limit.user_refill_seconds,
+        NOTIFY_RATE_LIMIT_USER_REFILL_SECONDS_ENV,
+        errors,
+    );
+    env_override(
+        &mut notify_rate_limit.guild_capacity,
+        NOTIFY_RATE_LIMIT_GUILD_CAPACITY_ENV,
+        errors,
+    );
+    env_override(
+        &mut notify_rate_limit.guild_refill_seconds,
+        NOTIFY_RATE_LIMIT_GUILD_REFILL_SECONDS_ENV,
+        errors,
+    );
+    env_override_ids(
+        &mut notify_rate_limit.exempt_role_ids,
+        NOTIFY_RATE_LIMIT_EXEMPT_ROLE_IDS_ENV,
+        errors,
+    );
+
+    let http_server = &mut file_config.http_server;
+    env_override(
+        &mut http_server.listen_address,
+        HTTP_SERVER_LISTEN_ADDRESS_ENV,
+        errors,
+    );
+}
+
+fn validate(file_config: FileConfig, errors: &mut Vec<String>) -> Option<Config> {
+    let FileConfig {
+        discord,
+        telegram,
+        notification,
+        animation,
+        voice_join,
+        voice_session_end,
+        voice_stream,
+        voice_video,
+        notify_rate_limit,
+        command_access,
+        http_server,
+    } = file_config;
+
+    let discord_bot_token = required(
+        discord.bot_token,
+        "discord.bot_token",
+        DISCORD_BOT_TOKEN_ENV,
+        errors,
+    );
+
+    let telegram_bot_token = required(
+        telegram.bot_token,
+        "telegram.bot_token",
+        TELEGRAM_BOT_TOKEN_ENV,
+        errors,
+    );
+    let telegram_chat_id = required(
+        telegram.chat_id,
+        "telegram.chat_id",
+        TELEGRAM_CHAT_ID_ENV,
+        errors,
+    );
+    let telegram_invite_link = required(
+        telegram.invite_link,
+        "telegram.invite_link",
+        TELEGRAM_INVITE_LINK_ENV,
+        errors,
+    );
+    let telegram_chat_routes = telegram
+        .chat_routes
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|chat_route| to_telegram_chat_route(chat_route, errors))
+        .collect();
+    let telegram_api_base_url = telegram
+        .api_base_url
+        .map_or(DEFAULT_TELEGRAM_API_BASE_URL.to_string(), |api_base_url| {
+            api_base_url.trim_end_matches('/').to_string()
+        });
+    if !telegram_api_base_url.starts_with("http://")
+        && !telegram_api_base_url.starts_with("https://")
+    {
+        errors.push(format!(
+            "telegram.api_base_url must be an http or https url: {}",
+            telegram_api_base_url
+        ));
+    }
+    let telegram_connect_timeout = positive_seconds(
+        telegram.connect_timeout_seconds,
+        DEFAULT_TELEGRAM_CONNECT_TIMEOUT_SECONDS,
+        "telegram.connect_timeout_seconds",
+        errors,
+    );
+    let telegram_request_timeout = positive_seconds(
+        telegram.request_timeout_seconds,
+        DEFAULT_TELEGRAM_REQUEST_TIMEOUT_SECONDS,
+        "telegram.request_timeout_seconds",
+        errors,
+    );
+    let telegram_health_check_interval = positive_seconds(
+        telegram.health_check_interval_seconds,
+        DEFAULT_TELEGRAM_HEALTH_CHECK_INTERVAL_SECONDS,
+        "telegram.health_check_interval_seconds",
+        errors,
+    );
+
+    let max_delivery_attempts = notification
+        .max_delivery_attempts
+        .unwrap_or(DEFAULT_NOTIFICATION_MAX_DELIVERY_ATTEMPTS);
+    if max_delivery_attempts == 0 {
+        errors.push("notification.max_delivery_attempts must be at least 1".to_string());
+    }
+    let retry_base_delay = positive_seconds(
+        notification.retry_base_delay_seconds,
+        DEFAULT_NOTIFICATION_RETRY_BASE_DELAY_SECONDS,
+        "notification.retry_base_delay_seconds",
+        errors,
+    );
+    let retry_max_delay = positive_seconds(
+        notification.retry_max_delay_seconds,
+        DEFAULT_NOTIFICATION_RETRY_MAX_DELAY_SECONDS,
+        "notification.retry_max_delay_seconds",
+        errors,
+    );
+    if retry_base_delay > retry_max_delay {
+        errors.push(
+            "notification.retry_base_delay_seconds cannot be greater than notification.retry_max_delay_seconds"
+                .to_string(),
+        );
+    }
+    let outbox_file = notification
+        .outbox_file
+        .unwrap_or(DEFAULT_NOTIFICATION_OUTBOX_FILE.to_string());
+    let filter_file = notification
+        .filter_file
+        .unwrap_or(DEFAULT_NOTIFICATION_FILTER_FILE.to_string());
+    let outbox_max_age = positive_seconds(
+        notification.outbox_max_age_seconds,
+        DEFAULT_NOTIFICATION_OUTBOX_MAX_AGE_SECONDS,
+        "notification.outbox_max_age_seconds",
+        errors,
+    );
+    let shutdown_drain_timeout = Duration::from_secs(
+        notification
+            .shutdown_drain_timeout_seconds
+            .unwrap_or(DEFAULT_NOTIFICATION_SHUTDOWN_DRAIN_TIMEOUT_SECONDS),
+    );
+
+    let animation_file = animation.file.unwrap_or(DEFAULT_ANIMATION_FILE.to_string());
+    let animation_reload_interval = positive_seconds(
+        animation.reload_interval_seconds,
+        DEFAULT_ANIMATION_RELOAD_INTERVAL_SECONDS,
+        "animation.reload_interval_seconds",
+        errors,
+    );
+    let animation_manager_role_ids = to_discord_ids(
+        animation.manager_role_ids.unwrap_or_default(),
+        "animation.manager_role_ids",
+        errors,
+    );
+
+    let voice_join_user_cooldown = Duration::from_secs(
+        voice_join
+            .user_cooldown_seconds
+            .unwrap_or(DEFAULT_VOICE_JOIN_USER_COOLDOWN_SECONDS),
+    );
+    let voice_join_channel_cooldown = Duration::from_secs(
+        voice_join
+            .channel_cooldown_seconds
+            .unwrap_or(DEFAULT_VOICE_JOIN_CHANNEL_COOLDOWN_SECONDS),
+    );
+    let voice_join_guild_cooldown = Duration::from_secs(
+        voice_join
+            .guild_cooldown_seconds
+            .unwrap_or(DEFAULT_VOICE_JOIN_GUILD_COOLDOWN_SECONDS),
+    );
+
+    let voice_session_end_enabled = voice_session_end
+        .enabled
+        .unwrap_or(DEFAULT_VOICE_SESSION_END_ENABLED);
+    let voice_session_end_message_template = message_template(
+        voice_session_end.message_template,
+        DEFAULT_VOICE_SESSION_END_MESSAGE_TEMPLATE,
+        message_helper::VOICE_SESSION_ENDED_PLACEHOLDERS,
+        "voice_session_end.message_template",
+        errors,
+    );
+
+    let voice_stream_config = to_voice_activity_config(
+        voice_stream,
+        DEFAULT_VOICE_STREAM_MESSAGE_TEMPLATE,
+        "voice_stream",
+        errors,
+    );
+    let voice_video_config = to_voice_activity_config(
+        voice_video,
+        DEFAULT_VOICE_VIDEO_MESSAGE_TEMPLATE,
+        "voice_video",
+        errors,
+    );
+
+    let notify_rate_limit_user_limit = TokenBucketLimit {
+        capacity: notify_rate_limit
+            .user_capacity
+            .unwrap_or(DEFAULT_NOTIFY_RATE_LIMIT_USER_CAPACITY),
+        refill_interval: positive_seconds(
+            notify_rate_limit.user_refill_seconds,
+            DEFAULT_NOTIFY_RATE_LIMIT_USER_REFILL_SECONDS,
+            "notify_rate_limit.user_refill_seconds",
+            errors,
+        ),
+    };
+    let notify_rate_limit_guild_limit = TokenBucketLimit {
+        capacity: notify_rate_limit
+            .guild_capacity
+            .unwrap_or(DEFAULT_NOTIFY_RATE_LIMIT_GUILD_CAPACITY),
+        refill_interval: positive_seconds(
+            notify_rate_limit.guild_refill_seconds,
+            DEFAULT_NOTIFY_RATE_LIMIT_GUILD_REFILL_SECONDS,
+            "notify_rate_limit.guild_refill_seconds",
+            errors,
+        ),
+    };
+    let notify_rate_limit_exempt_role_ids = to_discord_ids(
+        notify_rate_limit.exempt_role_ids.unwrap_or_default(),
+        "notify_rate_limit.exempt_role_ids",
+        errors,
+    );
+
+    let command_access_rules = command_access
+        .into_iter()
+        .filter_map(|access_rule| to_command_access_rule(access_rule, errors))
+        .collect();
+
+    let http_server_listen_address = http_server
+        .listen_address
+        .filter(|listen_address| !listen_address.trim().is_empty())
+        .and_then(
+            |listen_address| match listen_address.trim().parse::<SocketAddr>() {
+                Ok(listen_address) => Some(listen_address),
+                Err(_) => {
+                    errors.push(format!(
+                        "http_server.listen_address is not a valid address: {}",
+                        listen_address
+                    ));
+                    None
+                }
+            },
+        );
+
+    Some(Config {
+        discord_bot_token: discord_bot_token?,
+        telegram: TelegramConfig {
+            bot_token: telegram_bot_token?,
+            chat_id: telegram_chat_id?,
+            invite_link: telegram_invite_link?,
+            chat_routes: telegram_chat_routes,
+            api_base_url: telegram_api_base_url,
+            connect_timeout: telegram_connect_timeout,
+            request_timeout: telegram_request_timeout,
+            health_check_interval: telegram_health_check_interval,
+        },
+        notification: NotificationConfig {
+            max_delivery_attempts,
+            retry_base_delay,
+            retry_max_delay,
+            outbox_file,
+            filter_file,
+            outbox_max_age,
+            shutdown_drain_timeout,
+        },
+        animation: AnimationConfig {
+            file: animation_file,
+            reload_interval: animation_reload_interval,
+            manager_role_ids: animation_manager_role_ids,
+        },
+        voice_join: VoiceJoinConfig {
+            user_cooldown: voice_join_user_cooldown,
+            channel_cooldown: voice_join_channel_cooldown,
+            guild_cooldown: voice_join_guild_cooldown,
+        },
+        voice_session_end: VoiceSessionEndConfig {
+            enabled: voice_session_end_enabled,
+            message_template: voice_session_end_message_template,
+        },
+        voice_stream: voice_stream_config,
+        voice_video: voice_video_config,
+        notify_rate_limit: NotifyRateLimitConfig {
+            user_limit: notify_rate_limit_user_limit,
+            guild_limit: notify_rate_limit_guild_limit,
+            exempt_role_ids: notify_rate_limit_exempt_role_ids,
+        },
+        command_access_rules,
+        http_server: HttpServerConfig {
+            listen_address: http_server_listen_address,
+        },
+    })
+}
+
+fn env_override<T: FromStr>(field: &mut Option<T>, env_name: &str, errors: &mut Vec<String>) {
+    if let Ok(value) = env::var(env_name) {
+        match value.parse::<T>() {
+            Ok(parsed_value) => *field = Some(parsed_value),
+            Err(_) => errors.push(format!("Env variable has an invalid value: {}", env_name)),
+        }
+    }
+}
+
+fn env_override_ids(field: &mut Option<Vec<u64>>, env_name: &str, errors: &mut Vec<String>) {
+    if let Ok(value) = env::var(env_name) {
+        match value
+            .split(',')
+            .map(str::trim)
+            .filter(|id| !id.is_empty())
+            .map(parse_id)
+            .collect()
+        {
+            Ok(ids) => *field = Some(ids),
+            Err(why) => errors.push(format!(
+                "Env variable has an invalid value: {}. {}",
+                env_name, why
+            )),
+        }
+    }
+}
+
+fn to_discord_ids<T: From<NonZeroU64>>(
+    ids: Vec<u64>,
+    field_name: &str,
+    errors: &mut Vec<String>,
+) -> Vec<T> {
+    ids.into_iter()
+        .filter_map(|id| match NonZeroU64::new(id) {
+            Some(id) => Some(T::from(id)),
+            None => {
+                errors.push(format!("{} has an invalid discord id: {}", field_name, id));
+                None
+            }
+        })
+        .collect()
+}
+
+fn required(
+    field: Option<String>,
+    field_name: &str,
+    env_name: &str,
+    errors: &mut Vec<String>,
+) -> Option<String> {
+    match field {
+        Some(value) if !value.trim().is_empty() => Some(value),
+        _ => {
+            errors.push(format!(
+                "Missing {} in config file or env variable {}",
+                field_name, env_name
+            ));
+            None
+        }
+    }
+}
+
+fn positive_seconds(
+    field: Option<u64>,
+    default_seconds: u64,
+    field_name: &str,
+    errors: &mut Vec<String>,
+) -> Duration {
+    let seconds = field.unwrap_or(default_seconds);
+    if seconds == 0 {
+        errors.push(format!("{} must be greater than 0", field_name));
+    }
+
+    Duration::from_secs(seconds)
+}
+
+fn message_template(
+    field: Option<String>,
+    default_template: &str,
+    placeholders: &[&str],
+    field_name: &str,
+    errors: &mut Vec<String>,
+) -> String {
+    let template = field.unwrap_or(default_template.to_string());
+
+    let mut rest = template.as_str();
+    while let Some(placeholder_start) = rest.find('{') {
+        rest = &rest[placeholder_start + 1..];
+        let Some(placeholder_end) = rest.find('}') else {
+            errors.push(format!("{} has an unclosed {{", field_name));
+            break;
+        };
+
+        let placeholder = &rest[..placeholder_end];
+        if !placeholders.contains(&placeholder) {
+            errors.push(format!(
+                "{} has an unknown placeholder {{{}}}. Use {}",
+                field_name,
+                placeholder,
+                placeholders
+                    .iter()
+                    .map(|placeholder| format!("{{{}}}", placeholder))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        rest = &rest[placeholder_end + 1..];
+    }
+
+    template
+}
+
+fn to_voice_activity_config(
+    file_voice_activity: FileVoiceActivityConfig,
+    default_message_template: &str,
+    section_name: &str,
+    errors: &mut Vec<String>,
+) -> VoiceActivityConfig {
+    VoiceActivityConfig {
+        enabled: file_voice_activity
+            .enabled
+            .unwrap_or(DEFAULT_VOICE_ACTIVITY_ENABLED),
+        message_template: message_template(
+            file_voice_activity.message_template,
+            default_message_template,
+            message_helper::VOICE_ACTIVITY_PLACEHOLDERS,
+            &format!("{}.message_template", section_name),
+            errors,
+        ),
+        user_cooldown: Duration::from_secs(
+            file_voice_activity
+                .user_cooldown_seconds
+                .unwrap_or(DEFAULT_VOICE_ACTIVITY_USER_COOLDOWN_SECONDS),
+        ),
+        channel_cooldown: Duration::from_secs(
+            file_voice_activity
+                .channel_cooldown_seconds
+                .unwrap_or(DEFAULT_VOICE_ACTIVITY_CHANNEL_COOLDOWN_SECONDS),
+        ),
+        guild_cooldown: Duration::from_secs(
+            file_voice_activity
+                .guild_cooldown_seconds
+                .unwrap_or(DEFAULT_VOICE_ACTIVITY_GUILD_COOLDOWN_SECONDS),
+        ),
+    }
+}
+
+fn to_telegram_chat_route(
+    chat_route: FileTelegramChatRoute,
+    errors: &mut Vec<String>,
+) -> Option<TelegramChatRoute> {
+    let route_name = match chat_route.channel_id {
+        Some(channel_id) => format!("{}/{}", chat_route.guild_id, channel_id),
+        None => chat_route.guild_id.to_string(),
+    };
+
+    if chat_route.chat_ids.is_empty() {
+        errors.push(format!(
+            "telegram.chat_routes has no chat ids: {}",
+            route_name
+        ));
+        return None;
+    }
+
+    let guild_id = NonZeroU64::new(chat_route.guild_id).map(GuildId::from);
+    let channel_id = match chat_route.channel_id {
+        Some(channel_id) => NonZeroU64::new(channel_id).map(|id| Some(ChannelId::from(id))),
+        None => Some(None),
+    };
+
+    match (guild_id, channel_id) {
+        (Some(guild_id), Some(channel_id)) => Some(TelegramChatRoute {
+            guild_id,
+            channel_id,
+            chat_ids: chat_route.chat_ids,
+        }),
+        _ => {
+            errors.push(format!(
+                "telegram.chat_routes has an invalid discord id: {}",
+                route_name
+            ));
+            None
+        }
+    }
+}
+
+fn to_command_access_rule(
+    access_rule: FileCommandAccessRule,
+    errors: &mut Vec<String>,
+) -> Option<CommandAccessRule> {
+    let field_name = match access_rule.guild_id {
+        Some(guild_id) => format!("command_access {}/{}", access_rule.command, guild_id),
+        None => format!("command_access {}", access_rule.command),
+    };
+    let errors_count = errors.len();
+
+    if access_rule.command.trim().is_empty() {
+        errors.push("command_access has an empty command".to_string());
+    } else if slash_command::find_slash_command(&access_rule.command).is_none() {
+        errors.push(format!(
+            "{} has an unknown command. Use one of {}",
+            field_name,
+            slash_command::slash_command_names().join(", ")
+        ));
+    }
+    let guild_id = match access_rule.guild_id {
+        Some(guild_id) => match NonZeroU64::new(guild_id) {
+            Some(guild_id) => Some(GuildId::from(guild_id)),
+            None => {
+                errors.push(format!(
+                    "{} has an invalid discord id: {}",
+                    field_name, guild_id
+                ));
+                None
+            }
+        },
+        None => None,
+    };
+    let required_permissions = access_rule.required_permissions.iter().fold(
+        Permissions::empty(),
+        |permissions, permission_name| match Permissions::from_name(
+            &permission_name.trim().to_uppercase(),
+        ) {
+            Some(permission) => permissions | permission,
+            None => {
+                errors.push(format!(
+                    "{} has an unknown permission: {}",
+                    field_name, permission_name
+                ));
+                permissions
+            }
+        },
+    );
+    let allowed_role_ids = to_discord_ids(
+        access_rule.allowed_role_ids,
+        &format!("{} allowed_role_ids", field_name),
+        errors,
+    );
+    let denied_user_ids = to_discord_ids(
+        access_rule.denied_user_ids,
+        &format!("{} denied_user_ids", field_name),
+        errors,
+    );
+
+    if errors.len() > errors_count {
+        return None;
+    }
+
+    Some(CommandAccessRule {
+        command_name: access_rule.command,
+        guild_id,
+        allowed_role_ids,
+        required_permissions,
+        denied_user_ids,
+    })
+}
+
+fn parse_telegram_chat_routes(value: &str) -> Result<Vec<FileTelegramChatRoute>, String> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|route| !route.is_empty())
+        .map(|route| {
+            let (source, chat_ids) = route
+                .split_once('=')
+                .ok_or(format!("Route is missing '=': {}", route))?;
+
+            let (guild_id, channel_id) = match source.split_once('/') {
+                Some((guild_id, channel_id)) => (guild_id, Some(channel_id)),
+                None => (source, None),
+            };
+
+            Ok(FileTelegramChatRoute {
+                guild_id: parse_id(guild_id)?,
+                channel_id: channel_id.map(parse_id).transpose()?,
+                chat_ids: chat_ids
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|chat_id| !chat_id.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            })
+        })
+        .collect()
+}
+
+fn parse_id(value: &str) -> Result<u64, String> {
+    value
+        .trim()
+        .parse::<u64>()
+        .map_err(|_| format!("Invalid discord id: {}", value))
+}
+
+fn get() -> &'static Config {
+    CONFIG.get().expect("Error. Configuration not loaded")
+}
 
 pub fn discord_bot_token() -> String {
-    DISCORD_BOT_TOKEN.deref().to_string()
+    get().discord_bot_token.to_string()
 }
 
 pub fn telegram_bot_token() -> String {
-    TELEGRAM_BOT_TOKEN.deref().to_string()
+    get().telegram.bot_token.to_string()
 }
 
 pub fn telegram_chat_id() -> String {
-    TELEGRAM_CHAT_ID.deref().to_string()
+    get().telegram.chat_id.to_string()
+}
+
+pub fn telegram_chat_routes() -> &'static [TelegramChatRoute] {
+    &get().telegram.chat_routes
 }
 
 pub fn telegram_invite_link() -> String {
-    TELEGRAM_INVITE_LINK.deref().to_string()
+    get().telegram.invite_link.to_string()
+}
+
+pub fn telegram_api_base_url() -> String {
+    get().telegram.api_base_url.to_string()
+}
+
+pub fn telegram_connect_timeout() -> Duration {
+    get().telegram.connect_timeout
+}
+
+pub fn telegram_request_timeout() -> Duration {
+    get().telegram.request_timeout
+}
+
+pub fn telegram_health_check_interval() -> Duration {
+    get().telegram.health_check_interval
+}
+
+pub fn notification_max_delivery_attempts() -> u32 {
+    get().notification.max_delivery_attempts
+}
+
+pub fn notification_retry_base_delay() -> Duration {
+    get().notification.retry_base_delay
+}
+
+pub fn notification_retry_max_delay() -> Duration {
+    get().notification.retry_max_delay
+}
+
+pub fn notification_outbox_file() -> String {
+    get().notification.outbox_file.to_string()
+}
+
+pub fn notification_filter_file() -> String {
+    get().notification.filter_file.to_string()
+}
+
+pub fn notification_outbox_max_age() -> Duration {
+    get().notification.outbox_max_age
+}
+
+pub fn notification_shutdown_drain_timeout() -> Duration {
+    get().notification.shutdown_drain_timeout
+}
+
+pub fn animation_file() -> String {
+    get().animation.file.to_string()
+}
+
+pub fn animation_reload_interval() -> Duration {
+    get().animation.reload_interval
+}
+
+pub fn animation_manager_role_ids() -> &'static [RoleId] {
+    &get().animation.manager_role_ids
+}
+
+pub fn voice_join_user_cooldown() -> Duration {
+    get().voice_join.user_cooldown
+}
+
+pub fn voice_join_channel_cooldown() -> Duration {
+    get().voice_join.channel_cooldown
+}
+
+pub fn voice_join_guild_cooldown() -> Duration {
+    get().voice_join.guild_cooldown
+}
+
+pub fn voice_session_end_enabled() -> bool {
+    get().voice_session_end.enabled
+}
+
+pub fn voice_session_end_message_template() -> &'static str {
+    &get().voice_session_end.message_template
+}
+
+pub fn voice_activity(voice_activity: VoiceActivity) -> &'static VoiceActivityConfig {
+    match voice_activity {
+        VoiceActivity::Stream => &get().voice_stream,
+        VoiceActivity::Video => &get().voice_video,
+    }
+}
+
+pub fn notify_rate_limit_user_limit() -> TokenBucketLimit {
+    get().notify_rate_limit.user_limit
+}
+
+pub fn notify_rate_limit_guild_limit() -> TokenBucketLimit {
+    get().notify_rate_limit.guild_limit
+}
+
+pub fn notify_rate_limit_exempt_role_ids() -> &'static [RoleId] {
+    &get().notify_rate_limit.exempt_role_ids
+}
+
+pub fn command_access_rules() -> &'static [CommandAccessRule] {
+    &get().command_access_rules
+}
+
+pub fn http_server_listen_address() -> Option<SocketAddr> {
+    get().http_server.listen_address
 }
@@ -0,0 +1,181 @@
+use serenity::all::{ChannelId, GuildId, UserId, VoiceState};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config;
+use crate::voice_transition::VoiceActivity;
+
+static VOICE_JOIN_COOLDOWN_TRACKER: LazyLock<CooldownTracker> = LazyLock::new(CooldownTracker::new);
+static VOICE_STREAM_COOLDOWN_TRACKER: LazyLock<CooldownTracker> =
+    LazyLock::new(CooldownTracker::new);
+static VOICE_VIDEO_COOLDOWN_TRACKER: LazyLock<CooldownTracker> =
+    LazyLock::new(CooldownTracker::new);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CooldownScope {
+    User(UserId),
+    Channel(ChannelId),
+    Guild(GuildId),
+}
+
+impl fmt::Display for CooldownScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CooldownScope::User(user_id) => write!(f, "user {}", user_id),
+            CooldownScope::Channel(channel_id) => write!(f, "channel {}", channel_id),
+            CooldownScope::Guild(guild_id) => write!(f, "guild {}", guild_id),
+        }
+    }
+}
+
+pub struct CooldownRejection {
+    pub scope: CooldownScope,
+    pub remaining: Duration,
+}
+
+struct LastTrigger {
+    triggered_at: Instant,
+    window: Duration,
+}
+
+pub struct CooldownTracker {
+    last_triggered: Mutex<HashMap<CooldownScope, LastTrigger>>,
+    suppressed_count: AtomicU64,
+}
+
+impl CooldownTracker {
+    pub fn new() -> Self {
+        CooldownTracker {
+            last_triggered: Mutex::new(HashMap::new()),
+            suppressed_count: AtomicU64::new(0),
+        }
+    }
+
+    /// Check and record happen under one lock, so racing events cannot both get through.
+    pub fn try_trigger(
+        &self,
+        scopes: &[(CooldownScope, Duration)],
+    ) -> Result<(), CooldownRejection> {
+        let now = Instant::now();
+        let mut last_triggered = self.last_triggered.lock().unwrap();
+
+        for (scope, window) in scopes {
+            let Some(last_trigger) = last_triggered.get(scope) else {
+                continue;
+            };
+            let elapsed = now.duration_since(last_trigger.triggered_at);
+            if elapsed < *window {
+                self.suppressed_count.fetch_add(1, Ordering::Relaxed);
+                return Err(CooldownRejection {
+                    scope: *scope,
+                    remaining: *window - elapsed,
+                });
+            }
+        }
+
+        last_triggered.retain(|_, last_trigger| {
+            now.duration_since(last_trigger.triggered_at) < last_trigger.window
+        });
+
+        for (scope, window) in scopes {
+            if !window.is_zero() {
+                last_triggered.insert(
+                    *scope,
+                    LastTrigger {
+                        triggered_at: now,
+                        window: *window,
+                    },
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn suppressed_count(&self) -> u64 {
+        self.suppressed_count.load(Ordering::Relaxed)
+    }
+}
+
+pub fn try_trigger_voice_join_cooldown(voice_state: &VoiceState) -> Result<(), CooldownRejection> {
+    VOICE_JOIN_COOLDOWN_TRACKER.try_trigger(&voice_cooldown_scopes(
+        voice_state,
+        config::voice_join_user_cooldown(),
+        config::voice_join_channel_cooldown(),
+        config::voice_join_guild_cooldown(),
+    ))
+}
+
+pub fn voice_join_suppressed_count() -> u64 {
+    VOICE_JOIN_COOLDOWN_TRACKER.suppressed_count()
+}
+
+pub fn try_trigger_voice_activity_cooldown(
+    voice_activity: VoiceActivity,
+    voice_state: &VoiceState,
+) -> Result<(), CooldownRejection> {
+    let voice_activity_config = config::voice_activity(voice_activity);
+
+    voice_activity_cooldown_tracker(voice_activity).try_trigger(&voice_cooldown_scopes(
+        voice_state,
+        voice_activity_config.user_cooldown,
+        voice_activity_config.channel_cooldown,
+        voice_activity_config.guild_cooldown,
+    ))
+}
+
+pub fn voice_activity_suppressed_count(voice_activity: VoiceActivity) -> u64 {
+    voice_activity_cooldown_tracker(voice_activity).suppressed_count()
+}
+
+fn voice_activity_cooldown_tracker(voice_activity: VoiceActivity) -> &'static CooldownTracker {
+    match voice_activity {
+        VoiceActivity::Stream => &VOICE_STREAM_COOLDOWN_TRACKER,
+        VoiceActivity::Video => &VOICE_VIDEO_COOLDOWN_TRACKER,
+    }
+}
+
+fn voice_cooldown_scopes(
+    voice_state: &VoiceState,
+    user_cooldown: Duration,
+    channel_cooldown: Duration,
+    guild_cooldown: Duration,
+) -> Vec<(CooldownScope, Duration)> {
+    let mut scopes = vec![(CooldownScope::User(voice_state.user_id), user_cooldown)];
+    if let Some(channel_id) = voice_state.channel_id {
+        scopes.push((CooldownScope::Channel(channel_id), channel_cooldown));
+    }
+    if let Some(guild_id) = voice_state.guild_id {
+        scopes.push((CooldownScope::Guild(guild_id), guild_cooldown));
+    }
+
+    scopes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn short_window_call_keeps_longer_cooldowns() {
+        let cooldown_tracker = CooldownTracker::new();
+        let guild_scope = CooldownScope::Guild(GuildId::new(1));
+        let user_scope = CooldownScope::User(UserId::new(2));
+
+        assert!(cooldown_tracker
+            .try_trigger(&[(guild_scope, Duration::from_secs(300))])
+            .is_ok());
+        thread::sleep(Duration::from_millis(20));
+        assert!(cooldown_tracker
+            .try_trigger(&[(user_scope, Duration::from_millis(10))])
+            .is_ok());
+
+        assert!(cooldown_tracker
+            .try_trigger(&[(guild_scope, Duration::from_secs(300))])
+            .is_err());
+    }
+}
@@ -0,0 +1,382 @@
+use rand::Rng;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, OnceLock};
+use std::time::Duration;
+use tokio::sync::mpsc::{self, UnboundedReceiver, UnboundedSender};
+use tokio::sync::watch;
+use tracing::{error, info, warn};
+
+use crate::notification::{DeliveryError, Notification, NotificationSink};
+use crate::{config, notification, outbox};
+
+struct Delivery {
+    outbox_entry_id: u64,
+    sink: &'static dyn NotificationSink,
+    target: String,
+    notification: Notification,
+    attempt: u32,
+}
+
+static DELIVERY_QUEUE_SENDER: OnceLock<UnboundedSender<Delivery>> = OnceLock::new();
+static DELIVERY_QUEUE_CLOSED: AtomicBool = AtomicBool::new(false);
+static IN_FLIGHT_DELIVERIES: LazyLock<watch::Sender<usize>> =
+    LazyLock::new(|| watch::Sender::new(0));
+
+struct InFlightDelivery;
+
+impl InFlightDelivery {
+    fn start() -> Self {
+        IN_FLIGHT_DELIVERIES.send_modify(|count| *count += 1);
+
+        InFlightDelivery
+    }
+}
+
+impl Drop for InFlightDelivery {
+    fn drop(&mut self) {
+        IN_FLIGHT_DELIVERIES.send_modify(|count| *count -= 1);
+    }
+}
+
+pub fn start_delivery_queue() {
+    let (sender, receiver) = mpsc::unbounded_channel();
+
+    if DELIVERY_QUEUE_SENDER.set(sender).is_err() {
+        panic!("Error. Delivery queue already started");
+    }
+
+    tokio::spawn(run_delivery_queue(receiver));
+
+    replay_outbox();
+}
+
+pub async fn enqueue_delivery(
+    sink: &'static dyn NotificationSink,
+    target: String,
+    notification: Notification,
+) -> Result<(), String> {
+    if is_delivery_queue_closed() || delivery_queue_sender().is_closed() {
+        return Err("Delivery queue is closed".to_string());
+    }
+
+    let outbox_entry_id = outbox::add_entry(sink.name(), &target, &notification).await;
+
+    push_delivery(Delivery {
+        outbox_entry_id,
+        sink,
+        target,
+        notification,
+        attempt: 1,
+    });
+
+    Ok(())
+}
+
+/// Queued deliveries and pending retries are not attempted, they stay in the outbox.
+pub async fn close_delivery_queue(drain_timeout: Duration) -> Result<(), String> {
+    DELIVERY_QUEUE_CLOSED.store(true, Ordering::SeqCst);
+
+    let mut in_flight_receiver = IN_FLIGHT_DELIVERIES.subscribe();
+    let in_flight_count = *in_flight_receiver.borrow();
+    info!(
+        in_flight_count,
+        drain_timeout_ms = drain_timeout.as_millis(),
+        "Closed delivery queue, waiting for sends in flight"
+    );
+
+    let drained = tokio::time::timeout(
+        drain_timeout,
+        in_flight_receiver.wait_for(|count| *count == 0),
+    )
+    .await;
+
+    match drained {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!(
+            "{} sends still in flight after {} ms",
+            *IN_FLIGHT_DELIVERIES.borrow(),
+            drain_timeout.as_millis()
+        )),
+    }
+}
+
+fn is_delivery_queue_closed() -> bool {
+    DELIVERY_QUEUE_CLOSED.load(Ordering::SeqCst)
+}
+
+fn replay_outbox() {
+    for outbox_entry in outbox::load_pending_entries() {
+        let Some(sink) = notification::find_notification_sink(&outbox_entry.sink_name) else {
+            warn!(
+                outbox_entry_id = outbox_entry.id,
+                sink_name = outbox_entry.sink_name,
+                "Discarded outbox entry with an unknown sink"
+            );
+            outbox::remove_entry(outbox_entry.id);
+            continue;
+        };
+
+        info!(
+            outbox_entry_id = outbox_entry.id,
+            sink_name = outbox_entry.sink_name,
+            target = outbox_entry.target,
+            caption = outbox_entry.notification.message,
+            "Replaying outbox entry"
+        );
+
+        push_delivery(Delivery {
+            outbox_entry_id: outbox_entry.id,
+            sink,
+            target: outbox_entry.target,
+            notification: outbox_entry.notification,
+            attempt: 1,
+        });
+    }
+}
+
+fn delivery_queue_sender() -> &'static UnboundedSender<Delivery> {
+    DELIVERY_QUEUE_SENDER
+        .get()
+        .expect("Error. Delivery queue not started")
+}
+
+fn push_delivery(delivery: Delivery) {
+    if is_delivery_queue_closed() {
+        info!(
+            outbox_entry_id = delivery.outbox_entry_id,
+            "Delivery queue is closed. Notification left in the outbox"
+        );
+        return;
+    }
+
+    if delivery_queue_sender().send(delivery).is_err() {
+        error!("Delivery queue is closed. Notification discarded");
+    }
+}
+
+async fn run_delivery_queue(mut receiver: UnboundedReceiver<Delivery>) {
+    while let Some(delivery) = receiver.recv().await {
+        tokio::spawn(attempt_delivery(delivery));
+    }
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(
+        outbox_entry_id = delivery.outbox_entry_id,
+        sink_name = delivery.sink.name(),
+        target = %delivery.target,
+        attempt = delivery.attempt,
+        guild_id = ?delivery.notification.guild_id,
+        channel_id = ?delivery.notification.channel_id,
+    )
+)]
+async fn attempt_delivery(delivery: Delivery) {
+    // Counted before checking the queue is open, so a shutdown either sees this send or this
+    // send sees the shutdown.
+    let in_flight_delivery = InFlightDelivery::start();
+    if is_delivery_queue_closed() {
+        info!("Delivery queue is closed. Notification left in the outbox");
+        return;
+    }
+
+    let max_attempts = config::notification_max_delivery_attempts();
+
+    info!(max_attempts, "Delivering notification");
+
+    let delivery_result = delivery
+        .sink
+        .send_notification(&delivery.target, &delivery.notification)
+        .await;
+    drop(in_flight_delivery);
+
+    match delivery_result {
+        Ok(()) => {
+            outbox::remove_entry(delivery.outbox_entry_id);
+
+            info!("Delivered notification");
+        }
+        Err(DeliveryError::Permanent { trace }) => {
+            outbox::remove_entry(delivery.outbox_entry_id);
+
+            error!(
+                trace,
+                "Gave up delivering notification, error is not retryable"
+            );
+        }
+        Err(DeliveryError::Retryable { trace, .. }) if delivery.attempt >= max_attempts => {
+            outbox::remove_entry(delivery.outbox_entry_id);
+
+            error!(
+                trace,
+                max_attempts, "Gave up delivering notification, no attempts left"
+            );
+        }
+        Err(DeliveryError::Retryable { retry_after, trace }) => {
+            let delay = retry_after.unwrap_or_else(|| backoff_delay(delivery.attempt));
+
+            warn!(
+                trace,
+                max_attempts,
+                retry_in_ms = delay.as_millis(),
+                "Could not deliver notification, retrying"
+            );
+
+            tokio::time::sleep(delay).await;
+
+            push_delivery(Delivery {
+                attempt: delivery.attempt + 1,
+                ..delivery
+            });
+        }
+    }
+}
+
+/// Equal jitter: half of the delay is fixed and half is random.
+fn backoff_delay(attempt: u32) -> Duration {
+    let base_delay = config::notification_retry_base_delay();
+    let max_delay = config::notification_retry_max_delay();
+
+    let exponential_delay = base_delay
+        .checked_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+        .unwrap_or(max_delay)
+        .min(max_delay);
+
+    let half_delay = exponential_delay / 2;
+    let jitter = rand::thread_rng().gen_range(Duration::ZERO..=half_delay);
+
+    half_delay + jitter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::telegram;
+    use crate::test_support::{self, MockTelegramResponse};
+
+    const DELIVERY_TIMEOUT: Duration = Duration::from_secs(15);
+
+    async fn enqueue_telegram_delivery(chat_id: &str) {
+        let sink = notification::find_notification_sink(telegram::SINK_NAME).unwrap();
+        let notification = Notification::new(None, None, "https://example.com/animation.gif", "hi");
+
+        enqueue_delivery(sink, chat_id.to_string(), notification)
+            .await
+            .unwrap();
+    }
+
+    async fn wait_for_outbox_removal(chat_id: &str) {
+        test_support::wait_until(DELIVERY_TIMEOUT, || {
+            outbox::entry_ids_with_target(chat_id).is_empty()
+        })
+        .await;
+    }
+
+    #[tokio::test]
+    async fn delivered_notification_is_removed_from_outbox() {
+        test_support::init();
+        let chat_id = "chat-sent";
+        test_support::mock_telegram().respond_to_chat(chat_id, MockTelegramResponse::Sent);
+
+        enqueue_telegram_delivery(chat_id).await;
+        wait_for_outbox_removal(chat_id).await;
+
+        assert_eq!(
+            test_support::mock_telegram()
+                .requests_to_chat(chat_id)
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn server_error_is_retried_until_max_attempts() {
+        test_support::init();
+        let chat_id = "chat-server-error";
+        test_support::mock_telegram().respond_to_chat(chat_id, MockTelegramResponse::ServerError);
+
+        enqueue_telegram_delivery(chat_id).await;
+        wait_for_outbox_removal(chat_id).await;
+        tokio::time::sleep(test_support::RETRY_MAX_DELAY).await;
+
+        assert_eq!(
+            test_support::mock_telegram()
+                .requests_to_chat(chat_id)
+                .len(),
+            test_support::MAX_DELIVERY_ATTEMPTS as usize
+        );
+    }
+
+    #[tokio::test]
+    async fn too_many_requests_waits_for_retry_after() {
+        test_support::init();
+        let chat_id = "chat-too-many-requests";
+        // Longer than any backoff delay, so only honouring retry_after explains the wait.
+        let retry_after_seconds = 3;
+        let mock_telegram = test_support::mock_telegram();
+        mock_telegram.respond_to_chat(
+            chat_id,
+            MockTelegramResponse::TooManyRequests {
+                retry_after_seconds,
+            },
+        );
+
+        enqueue_telegram_delivery(chat_id).await;
+        test_support::wait_until(DELIVERY_TIMEOUT, || {
+            !mock_telegram.requests_to_chat(chat_id).is_empty()
+        })
+        .await;
+        mock_telegram.respond_to_chat(chat_id, MockTelegramResponse::Sent);
+        wait_for_outbox_removal(chat_id).await;
+
+        let requests = mock_telegram.requests_to_chat(chat_id);
+        assert_eq!(requests.len(), 2);
+        assert!(
+            requests[1].duration_since(requests[0])
+                >= Duration::from_secs(u64::from(retry_after_seconds))
+        );
+    }
+
+    #[tokio::test]
+    async fn bad_request_is_not_retried() {
+        test_support::init();
+        let chat_id = "chat-bad-request";
+        test_support::mock_telegram().respond_to_chat(chat_id, MockTelegramResponse::BadRequest);
+
+        enqueue_telegram_delivery(chat_id).await;
+        wait_for_outbox_removal(chat_id).await;
+        tokio::time::sleep(test_support::RETRY_MAX_DELAY).await;
+
+        assert_eq!(
+            test_support::mock_telegram()
+                .requests_to_chat(chat_id)
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn backoff_delay_is_between_half_and_full_exponential_delay() {
+        test_support::init();
+
+        for attempt in 1..=8 {
+            let exponential_delay = (test_support::RETRY_BASE_DELAY * 2u32.pow(attempt - 1))
+                .min(test_support::RETRY_MAX_DELAY);
+
+            for _ in 0..100 {
+                let delay = backoff_delay(attempt);
+                assert!(delay >= exponential_delay / 2, "attempt {}", attempt);
+                assert!(delay <= exponential_delay, "attempt {}", attempt);
+            }
+        }
+    }
+
+    #[test]
+    fn backoff_delay_is_capped_at_max_delay() {
+        test_support::init();
+
+        for attempt in [10, 31, 32, 64, u32::MAX] {
+            assert!(backoff_delay(attempt) <= test_support::RETRY_MAX_DELAY);
+        }
+    }
+}
@@ -0,0 +1,13 @@
+use std::fs;
+use std::io::Write;
+
+/// Writes a temp file next to `file_path` and renames it over the old one.
+pub fn write_atomically(file_path: &str, contents: &[u8]) -> std::io::Result<()> {
+    let temp_file_path = format!("{}.tmp", file_path);
+
+    let mut temp_file = fs::File::create(&temp_file_path)?;
+    temp_file.write_all(contents)?;
+    temp_file.sync_all()?;
+
+    fs::rename(&temp_file_path, file_path)
+}
@@ -0,0 +1,62 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use tracing::{info, warn};
+
+use crate::{config, telegram};
+
+static GATEWAY_READY: AtomicBool = AtomicBool::new(false);
+static GATEWAY_READY_RECEIVED: AtomicBool = AtomicBool::new(false);
+static TELEGRAM_REACHABLE: AtomicBool = AtomicBool::new(false);
+
+pub struct Readiness {
+    pub gateway_ready: bool,
+    pub telegram_reachable: bool,
+}
+
+impl Readiness {
+    pub fn is_ready(&self) -> bool {
+        self.gateway_ready && self.telegram_reachable
+    }
+}
+
+pub fn record_gateway_ready() -> bool {
+    GATEWAY_READY.store(true, Ordering::Relaxed);
+
+    GATEWAY_READY_RECEIVED.swap(true, Ordering::Relaxed)
+}
+
+pub fn record_gateway_connected(is_connected: bool) {
+    GATEWAY_READY.store(
+        is_connected && GATEWAY_READY_RECEIVED.load(Ordering::Relaxed),
+        Ordering::Relaxed,
+    );
+}
+
+pub fn readiness() -> Readiness {
+    Readiness {
+        gateway_ready: GATEWAY_READY.load(Ordering::Relaxed),
+        telegram_reachable: TELEGRAM_REACHABLE.load(Ordering::Relaxed),
+    }
+}
+
+pub fn start_telegram_health_check() {
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(config::telegram_health_check_interval());
+
+        loop {
+            interval.tick().await;
+
+            let is_reachable = match telegram::check_telegram_reachable().await {
+                Ok(()) => true,
+                Err(why) => {
+                    warn!(trace = why, "Telegram health check failed");
+                    false
+                }
+            };
+
+            let was_reachable = TELEGRAM_REACHABLE.swap(is_reachable, Ordering::Relaxed);
+            if is_reachable && !was_reachable {
+                info!("Telegram is reachable");
+            }
+        }
+    });
+}
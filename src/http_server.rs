@@ -0,0 +1,63 @@
+use axum::http::header::CONTENT_TYPE;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::routing::get;
+use axum::Router;
+use prometheus::TEXT_FORMAT;
+use tokio::net::TcpListener;
+use tracing::{error, info};
+
+use crate::{config, health, metrics};
+
+pub async fn start_http_server() -> Result<(), String> {
+    let Some(listen_address) = config::http_server_listen_address() else {
+        info!("Http server disabled");
+        return Ok(());
+    };
+
+    let listener = TcpListener::bind(listen_address)
+        .await
+        .map_err(|why| format!("Unable to listen on {}. {:?}", listen_address, why))?;
+
+    let router = Router::new()
+        .route("/metrics", get(get_metrics))
+        .route("/healthz", get(get_healthz))
+        .route("/readyz", get(get_readyz));
+
+    health::start_telegram_health_check();
+
+    tokio::spawn(async move {
+        info!(%listen_address, "Http server listening");
+
+        if let Err(why) = axum::serve(listener, router).await {
+            error!(trace = ?why, "Http server error");
+        }
+    });
+
+    Ok(())
+}
+
+async fn get_metrics() -> impl IntoResponse {
+    ([(CONTENT_TYPE, TEXT_FORMAT)], metrics::render())
+}
+
+async fn get_healthz() -> impl IntoResponse {
+    "ok"
+}
+
+async fn get_readyz() -> impl IntoResponse {
+    let readiness = health::readiness();
+    let status_code = if readiness.is_ready() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (
+        status_code,
+        format!(
+            "gateway_ready: {}\ntelegram_reachable: {}\n",
+            readiness.gateway_ready, readiness.telegram_reachable
+        ),
+    )
+}
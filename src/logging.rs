@@ -0,0 +1,29 @@
+use std::env;
+use tracing_subscriber::EnvFilter;
+
+const LOG_FORMAT_ENV: &str = "LOG_FORMAT";
+
+const DEFAULT_LOG_FILTER: &str = "info";
+
+pub fn init_logging() {
+    let env_filter =
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+    let log_format = env::var(LOG_FORMAT_ENV).unwrap_or_default();
+
+    let subscriber = tracing_subscriber::fmt().with_env_filter(env_filter);
+    match log_format.as_str() {
+        "json" => subscriber
+            .json()
+            .with_current_span(true)
+            .with_span_list(false)
+            .init(),
+        _ => subscriber.init(),
+    }
+
+    if !matches!(log_format.as_str(), "" | "text" | "json") {
+        tracing::warn!(
+            log_format,
+            "Unknown log format, using text. Use text or json"
+        );
+    }
+}
@@ -1,169 +1,537 @@
 use serenity::all::{
-    Client as SerenityClient, Command, Context, EventHandler, GatewayIntents, Interaction, Ready,
+    ChannelId, Client as SerenityClient, Command, CommandInteraction, ComponentInteraction,
+    ConnectionStage, Context, CreateAutocompleteResponse, CreateInteractionResponse, EventHandler,
+    GatewayIntents, Guild, GuildId, Interaction, Ready, ResumedEvent, ShardStageUpdateEvent,
     VoiceState,
 };
+use std::time::Instant;
+use tracing::{error, info, warn};
 
+use crate::commands::command_error::CommandError;
 use crate::commands::serenity_command_helper;
+use crate::notification::Notification;
+use crate::voice_transition::{VoiceAction, VoiceActivity};
 
 mod animation;
 mod commands;
 mod config;
+mod cooldown;
+mod delivery_queue;
+mod file_helper;
+mod health;
+mod http_server;
+mod logging;
 mod message_helper;
+mod metrics;
+mod notification;
+mod notification_filter;
+mod outbox;
+mod rate_limiter;
 mod serenity_model_helper;
+mod shutdown;
 mod telegram;
+#[cfg(test)]
+mod test_support;
+mod voice_session;
+mod voice_transition;
+
+const EXIT_CODE_CLIENT_ERROR: i32 = 1;
+const EXIT_CODE_DRAIN_TIMEOUT: i32 = 2;
 
 struct Handler;
 
 #[serenity::async_trait]
 impl EventHandler for Handler {
     async fn ready(&self, ctx: Context, ready: Ready) {
-        let user_name = ready.user.name.as_str();
-
-        println!("Start. Ready event. User: {}", user_name);
+        info!(user_name = %ready.user.name, "Connected to discord servers successfully");
 
-        println!(
-            "User {} connected to discord servers successfully",
-            user_name
-        );
-
-        println!("Creating application commands");
+        if health::record_gateway_ready() {
+            metrics::record_gateway_reconnect("new_session");
+        }
 
-        if let Err(why) = Command::set_global_commands(
-            &ctx,
-            vec![
-                commands::notify_command::register(),
-                commands::animations_command::register(),
-                commands::invite_command::register(),
-            ],
-        )
-        .await
+        if let Err(why) =
+            Command::set_global_commands(&ctx, commands::slash_command::register_slash_commands())
+                .await
         {
-            println!("Error. Could not create commands. Trace: {:?}", why)
+            error!(trace = ?why, "Could not create commands")
         } else {
-            println!("Created commands");
-
-            println!("End. Ready event. User: {}", user_name);
+            info!("Created commands");
         }
     }
 
+    async fn resume(&self, _ctx: Context, _resumed_event: ResumedEvent) {
+        info!("Resumed gateway session");
+
+        metrics::record_gateway_reconnect("resume");
+    }
+
+    async fn shard_stage_update(&self, _ctx: Context, event: ShardStageUpdateEvent) {
+        info!(
+            shard_id = %event.shard_id,
+            old_stage = %event.old,
+            new_stage = %event.new,
+            "Gateway connection stage changed"
+        );
+
+        health::record_gateway_connected(event.new == ConnectionStage::Connected);
+    }
+
+    #[tracing::instrument(
+        skip_all,
+        fields(
+            guild_id = ?new_voice_state.guild_id,
+            channel_id = ?new_voice_state.channel_id,
+            user_id = %new_voice_state.user_id,
+            latency_ms = tracing::field::Empty,
+        )
+    )]
     async fn voice_state_update(
         &self,
         ctx: Context,
         old_voice_state: Option<VoiceState>,
         new_voice_state: VoiceState,
     ) {
+        let started_at = Instant::now();
         let user_name: &str =
-            &serenity_model_helper::get_user_name_from_voice_state(&ctx, &new_voice_state)
-                .await;
+            &serenity_model_helper::get_user_name_from_voice_state(&ctx, &new_voice_state).await;
 
         let channel_name: &str =
-            &serenity_model_helper::get_channel_name_from_voice_state(&ctx, &new_voice_state)
-                .await;
+            &serenity_model_helper::get_channel_name_from_voice_state(&ctx, &new_voice_state).await;
 
         let guild_name: &str =
-            &serenity_model_helper::get_guild_name_from_voice_state(&ctx, &new_voice_state)
-                .await;
+            &serenity_model_helper::get_guild_name_from_voice_state(&ctx, &new_voice_state).await;
+
+        let voice_state_change = voice_transition::classify_voice_state_update(
+            old_voice_state.as_ref(),
+            &new_voice_state,
+        );
 
-        println!(
-            "Start. Voice state update event. UserName: {}. ChannelName: {}. GuildName: {}",
-            user_name, channel_name, guild_name
+        info!(
+            user_name,
+            channel_name,
+            guild_name,
+            previous_channel_id = ?voice_state_change.previous_channel_id,
+            transitions = ?voice_state_change.transitions,
+            "Voice state update"
         );
+        metrics::record_voice_event_received();
 
-        if old_voice_state.is_none() && new_voice_state.channel_id.is_some() {
-            let member_count =
-                serenity_model_helper::get_voice_channel_members_count_from_voice_state(
-                    &ctx,
-                    &new_voice_state,
-                )
-                .await;
-
-            if member_count <= 1 {
-                let animation_url: &str = &animation::get_random_animation_url();
-                let message: &str = &message_helper::build_voice_channel_notification_message(
-                    user_name,
-                    channel_name,
-                    guild_name,
-                );
-                telegram::send_notification_to_telegram(animation_url, message);
-
-                println!(
-                    "End. Voice state update event. UserName: {}. ChannelName: {}. GuildName: {}",
-                    user_name, channel_name, guild_name
-                );
-            } else {
-                println!(
-                    "Discarded. Voice state update event. Discarded because there is more than one member in voice channel. UserName: {}. ChannelName: {}. GuildName: {}",
-                    user_name,
-                    channel_name,
-                    guild_name
-                );
+        let mut notified = false;
+        let mut discard_reason = "no_action";
+        for voice_action in voice_state_change.actions() {
+            let voice_action_result = match voice_action {
+                VoiceAction::EndSession => match voice_state_change.previous_channel_id {
+                    Some(left_channel_id) => {
+                        notify_voice_session_ended(
+                            &ctx,
+                            &new_voice_state,
+                            left_channel_id,
+                            user_name,
+                            guild_name,
+                        )
+                        .await
+                    }
+                    None => Err("no_action"),
+                },
+                VoiceAction::NotifyJoin => {
+                    notify_voice_join(&ctx, &new_voice_state, user_name, channel_name, guild_name)
+                        .await
+                }
+                VoiceAction::NotifyActivity(voice_activity) => {
+                    notify_voice_activity(
+                        &ctx,
+                        voice_activity,
+                        &new_voice_state,
+                        user_name,
+                        channel_name,
+                        guild_name,
+                    )
+                    .await
+                }
+            };
+
+            match voice_action_result {
+                Ok(()) => notified = true,
+                Err(reason) => discard_reason = reason,
             }
+        }
+
+        tracing::Span::current().record("latency_ms", started_at.elapsed().as_millis() as u64);
+        if notified {
+            info!("Voice state update done");
+            metrics::record_voice_event_notified();
         } else {
-            println!(
-                "Discarded. Voice state update event. Discarded because there is an old state for user. UserName: {}. ChannelName: {}. GuildName: {}",
-                user_name,
-                channel_name,
-                guild_name
-            );
+            info!(reason = discard_reason, "Discarded voice state update");
+            metrics::record_voice_event_discarded(discard_reason);
         }
     }
 
+    async fn guild_create(&self, _ctx: Context, guild: Guild, _is_new: Option<bool>) {
+        voice_transition::record_guild_voice_states(&guild);
+    }
+
     async fn interaction_create(&self, ctx: Context, interaction: Interaction) {
         if let Interaction::Command(command) = interaction {
-            let command_name: &str = command.data.name.as_str();
-            let user_name: &str = command.user.name.as_str();
-            let channel_name: &str =
-                &serenity_model_helper::get_channel_name_from_application_command(&ctx, &command)
-                    .await;
-
-            println!(
-                "Start. Application command interaction. CommandName: {}, UserName: {}. ChannelName: {}",
-                command_name,
-                user_name,
-                channel_name
-            );
+            run_command(&ctx, &command).await;
+        } else if let Interaction::Autocomplete(command) = interaction {
+            run_autocomplete(&ctx, &command).await;
+        } else if let Interaction::Component(component) = interaction {
+            run_component(&ctx, &component).await;
+        }
+    }
+}
 
-            let command_interaction_result = match command_name {
-                commands::notify_command::COMMAND_NAME => {
-                    commands::notify_command::run(&ctx, &command).await
-                }
-                commands::animations_command::COMMAND_NAME => {
-                    commands::animations_command::run(&ctx, &command).await
-                }
-                commands::invite_command::COMMAND_NAME => {
-                    commands::invite_command::run(&ctx, &command).await
-                }
-                _ => {
-                    serenity_command_helper::respond_interaction_with_string(
-                        &ctx,
-                        &command,
-                        "Error! Command does not exist!",
-                    )
-                    .await
-                }
-            };
+async fn notify_voice_join(
+    ctx: &Context,
+    voice_state: &VoiceState,
+    user_name: &str,
+    channel_name: &str,
+    guild_name: &str,
+) -> Result<(), &'static str> {
+    if !is_voice_channel_notified(ctx, voice_state.guild_id, voice_state.channel_id) {
+        return Err("channel_excluded");
+    }
+
+    let Some(member_count) =
+        serenity_model_helper::get_voice_channel_members_count_from_voice_state(ctx, voice_state)
+            .await
+    else {
+        return Err("member_count_unknown");
+    };
+    if member_count > 1 {
+        info!(member_count, "Voice channel is not empty");
+        return Err("channel_not_empty");
+    }
+
+    if let Some(channel_id) = voice_state.channel_id {
+        voice_session::start_voice_session(channel_id);
+    }
+
+    if let Err(cooldown_rejection) = cooldown::try_trigger_voice_join_cooldown(voice_state) {
+        info!(
+            scope = %cooldown_rejection.scope,
+            remaining_seconds = cooldown_rejection.remaining.as_secs(),
+            suppressed_count = cooldown::voice_join_suppressed_count(),
+            "Voice join is cooling down"
+        );
+        return Err("cooldown");
+    }
+
+    let animation_url: &str = &animation::get_random_animation_url();
+    let message: &str = &message_helper::build_voice_channel_notification_message(
+        user_name,
+        channel_name,
+        guild_name,
+    );
+    if let Err(why) = notification::dispatch_notification(&Notification::new(
+        voice_state.guild_id,
+        voice_state.channel_id,
+        animation_url,
+        message,
+    ))
+    .await
+    {
+        error!(trace = why, "Could not dispatch notification");
+        return Err("dispatch_error");
+    }
+
+    info!("Dispatched voice join notification");
+    Ok(())
+}
+
+async fn notify_voice_activity(
+    ctx: &Context,
+    voice_activity: VoiceActivity,
+    voice_state: &VoiceState,
+    user_name: &str,
+    channel_name: &str,
+    guild_name: &str,
+) -> Result<(), &'static str> {
+    let voice_activity_config = config::voice_activity(voice_activity);
+    if !voice_activity_config.enabled {
+        return Err("activity_disabled");
+    }
+
+    if !is_voice_channel_notified(ctx, voice_state.guild_id, voice_state.channel_id) {
+        return Err("channel_excluded");
+    }
+
+    if let Err(cooldown_rejection) =
+        cooldown::try_trigger_voice_activity_cooldown(voice_activity, voice_state)
+    {
+        info!(
+            %voice_activity,
+            scope = %cooldown_rejection.scope,
+            remaining_seconds = cooldown_rejection.remaining.as_secs(),
+            suppressed_count = cooldown::voice_activity_suppressed_count(voice_activity),
+            "Voice activity is cooling down"
+        );
+        return Err("cooldown");
+    }
+
+    let animation_url: &str = &animation::get_random_animation_url();
+    let message: &str = &message_helper::build_voice_activity_message(
+        &voice_activity_config.message_template,
+        user_name,
+        channel_name,
+        guild_name,
+    );
+    if let Err(why) = notification::dispatch_notification(&Notification::new(
+        voice_state.guild_id,
+        voice_state.channel_id,
+        animation_url,
+        message,
+    ))
+    .await
+    {
+        error!(trace = why, "Could not dispatch notification");
+        return Err("dispatch_error");
+    }
+
+    info!(%voice_activity, "Dispatched voice activity notification");
+    Ok(())
+}
+
+async fn notify_voice_session_ended(
+    ctx: &Context,
+    voice_state: &VoiceState,
+    left_channel_id: ChannelId,
+    user_name: &str,
+    guild_name: &str,
+) -> Result<(), &'static str> {
+    if !is_voice_channel_notified(ctx, voice_state.guild_id, Some(left_channel_id)) {
+        return Err("channel_excluded");
+    }
+
+    let Some(member_count) =
+        serenity_model_helper::get_voice_channel_members_count_from_channel_id(
+            ctx,
+            &left_channel_id,
+        )
+        .await
+    else {
+        return Err("member_count_unknown");
+    };
+    if member_count > 0 {
+        return Err("channel_not_empty");
+    }
+
+    let Some(session_duration) = voice_session::end_voice_session(left_channel_id) else {
+        info!(
+            left_channel_id = %left_channel_id,
+            "Voice channel emptied, its session start is unknown"
+        );
+        return Err("session_start_unknown");
+    };
+
+    info!(
+        left_channel_id = %left_channel_id,
+        session_duration_seconds = session_duration.as_secs(),
+        "Voice channel emptied"
+    );
+
+    if !config::voice_session_end_enabled() {
+        return Err("session_end_disabled");
+    }
+
+    let channel_name: &str =
+        &serenity_model_helper::get_channel_name_from_channel_id(ctx, &left_channel_id).await;
+
+    let animation_url: &str = &animation::get_random_animation_url();
+    let message: &str = &message_helper::build_voice_session_ended_message(
+        config::voice_session_end_message_template(),
+        user_name,
+        channel_name,
+        guild_name,
+        session_duration,
+    );
+    if let Err(why) = notification::dispatch_notification(&Notification::new(
+        voice_state.guild_id,
+        Some(left_channel_id),
+        animation_url,
+        message,
+    ))
+    .await
+    {
+        error!(trace = why, "Could not dispatch notification");
+        return Err("dispatch_error");
+    }
+
+    info!("Dispatched voice session ended notification");
+    Ok(())
+}
+
+fn is_voice_channel_notified(
+    ctx: &Context,
+    guild_id: Option<GuildId>,
+    channel_id: Option<ChannelId>,
+) -> bool {
+    let (Some(guild_id), Some(channel_id)) = (guild_id, channel_id) else {
+        return true;
+    };
+
+    let voice_channel_location =
+        serenity_model_helper::get_voice_channel_location(ctx, guild_id, channel_id);
+    let is_notified =
+        notification_filter::is_notification_allowed(guild_id, &voice_channel_location);
+    if !is_notified {
+        info!(
+            %channel_id,
+            category_id = ?voice_channel_location.category_id,
+            is_afk_channel = voice_channel_location.is_afk_channel,
+            "Voice channel is excluded from notifications"
+        );
+    }
+
+    is_notified
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(
+        command_name = %command.data.name,
+        guild_id = ?command.guild_id,
+        channel_id = %command.channel_id,
+        user_id = %command.user.id,
+    )
+)]
+async fn run_command(ctx: &Context, command: &CommandInteraction) {
+    let started_at = Instant::now();
+    let command_name: &str = command.data.name.as_str();
+
+    info!(user_name = %command.user.name, "Application command interaction");
+
+    let command_interaction_result = match (
+        commands::command_access::check_command_access(command),
+        commands::slash_command::find_slash_command(command_name),
+    ) {
+        (Err(access_denial), _) => Err(CommandError::NotAllowed(access_denial.to_string())),
+        (Ok(()), Some(slash_command)) => slash_command.run(ctx, command).await,
+        (Ok(()), None) => Err(CommandError::Internal(format!(
+            "Command does not exist: {}",
+            command_name
+        ))),
+    };
+
+    let latency_ms = started_at.elapsed().as_millis();
+    if let Err(command_error) = command_interaction_result {
+        commands::command_error::report_command_error(ctx, command, &command_error).await;
+        info!(latency_ms, "Application command failed");
+        metrics::record_command(command_name, command_error.kind());
+    } else {
+        info!(latency_ms, "Application command done");
+        metrics::record_command(command_name, metrics::SUCCESS_OUTCOME);
+    }
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(command_name = %command.data.name, user_id = %command.user.id)
+)]
+async fn run_autocomplete(ctx: &Context, command: &CommandInteraction) {
+    if let Err(access_denial) = commands::command_access::check_command_access(command) {
+        info!(reason = %access_denial, "Discarded autocomplete, access is not allowed");
+        if let Err(why) = serenity_command_helper::respond_interaction(ctx, command, || {
+            CreateInteractionResponse::Autocomplete(CreateAutocompleteResponse::new())
+        })
+        .await
+        {
+            warn!(error = %why, "Could not respond to autocomplete");
+        }
+        return;
+    }
 
-            if let Err(_) = command_interaction_result {
-                println!("Error. Failure running command. CommandName: {}, UserName: {}. ChannelName: {}.",
-                     command_name,
-                     user_name,
-                     channel_name,
-                );
-            } else {
-                println!(
-                    "End. Application command interaction. CommandName: {}, UserName: {}. ChannelName: {}",
-                    command_name,
-                    user_name,
-                    channel_name
-                );
+    let autocomplete_result = match commands::slash_command::find_slash_command(&command.data.name)
+    {
+        Some(slash_command) => slash_command.autocomplete(ctx, command).await,
+        None => Ok(()),
+    };
+
+    if let Err(command_error) = autocomplete_result {
+        warn!(error = %command_error, "Could not run autocomplete");
+    }
+}
+
+#[tracing::instrument(
+    skip_all,
+    fields(custom_id = %component.data.custom_id, user_id = %component.user.id)
+)]
+async fn run_component(ctx: &Context, component: &ComponentInteraction) {
+    let started_at = Instant::now();
+    let custom_id: &str = component.data.custom_id.as_str();
+
+    info!(user_name = %component.user.name, "Component interaction");
+
+    let component_interaction_result =
+        if custom_id.starts_with(commands::animations_command::LIST_COMPONENT_ID_PREFIX) {
+            match commands::command_access::check_component_access(
+                component,
+                commands::animations_command::COMMAND_NAME,
+            ) {
+                Ok(()) => commands::animations_command::run_list_component(ctx, component).await,
+                Err(access_denial) => Err(CommandError::NotAllowed(access_denial.to_string())),
             }
+        } else {
+            serenity_command_helper::respond_component_interaction_with_ephemeral_string(
+                ctx,
+                component,
+                "Error! Button does not exist!",
+            )
+            .await
+        };
+
+    let latency_ms = started_at.elapsed().as_millis();
+    match component_interaction_result {
+        Ok(()) => info!(latency_ms, "Component interaction done"),
+        Err(CommandError::NotAllowed(reason)) => {
+            info!(
+                reason,
+                latency_ms, "Discarded component interaction, access is not allowed"
+            );
+            if let Err(why) =
+                serenity_command_helper::respond_not_allowed_component_interaction(ctx, component)
+                    .await
+            {
+                error!(error = %why, "Could not respond to component interaction");
+            }
+        }
+        Err(command_error) => {
+            error!(error = %command_error, latency_ms, "Component interaction failed")
         }
     }
 }
 
 #[tokio::main]
 async fn main() {
-    println!("Start. main");
+    logging::init_logging();
+
+    info!("Starting");
+
+    if let Err(errors) = config::load_config() {
+        error!(problems_count = errors.len(), "Invalid configuration");
+        for error in errors {
+            error!(problem = error, "Invalid configuration value");
+        }
+        std::process::exit(1);
+    }
+
+    if let Err(why) = animation::load_animations() {
+        error!(trace = why, "Could not load animations");
+        std::process::exit(1);
+    }
+    animation::start_animations_watcher();
+
+    if let Err(why) = notification_filter::load_notification_filters() {
+        error!(trace = why, "Could not load notification filters");
+        std::process::exit(1);
+    }
+
+    if let Err(why) = http_server::start_http_server().await {
+        error!(trace = why, "Could not start http server");
+        std::process::exit(1);
+    }
+
+    delivery_queue::start_delivery_queue();
 
     let intents = GatewayIntents::GUILD_VOICE_STATES
         | GatewayIntents::GUILD_MESSAGES
@@ -177,9 +545,32 @@ async fn main() {
             .await
             .expect("Error. Could not create Serenity client");
 
-    if let Err(why) = serenity_client.start().await {
-        println!("Error. Serenity client error. Trace: {:?}", why);
-    } else {
-        println!("End. main");
+    let shard_manager = serenity_client.shard_manager.clone();
+
+    let client_result = tokio::select! {
+        client_result = serenity_client.start() => client_result,
+        signal = shutdown::wait_for_shutdown_signal() => {
+            info!(signal, "Shutting down");
+            shard_manager.shutdown_all().await;
+            Ok(())
+        }
+    };
+
+    if let Err(why) = &client_result {
+        error!(trace = ?why, "Serenity client error");
+    }
+
+    let drain_result =
+        delivery_queue::close_delivery_queue(config::notification_shutdown_drain_timeout()).await;
+    outbox::flush().await;
+
+    if client_result.is_err() {
+        std::process::exit(EXIT_CODE_CLIENT_ERROR);
     }
+    if let Err(why) = drain_result {
+        warn!(trace = why, "Stopped before every notification was sent");
+        std::process::exit(EXIT_CODE_DRAIN_TIMEOUT);
+    }
+
+    info!("Stopped");
 }
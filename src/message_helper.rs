@@ -1,3 +1,5 @@
+use std::time::Duration;
+
 pub fn build_voice_channel_notification_message(
     user_name: &str,
     channel_name: &str,
@@ -19,3 +21,143 @@ pub fn build_text_channel_notification_message(
         user_name, channel_name, guild_name
     )
 }
+
+pub const VOICE_ACTIVITY_PLACEHOLDERS: &[&str] = &["user", "channel", "guild"];
+
+pub const VOICE_SESSION_ENDED_PLACEHOLDERS: &[&str] = &["user", "channel", "guild", "duration"];
+
+pub fn build_voice_session_ended_message(
+    template: &str,
+    user_name: &str,
+    channel_name: &str,
+    guild_name: &str,
+    session_duration: Duration,
+) -> String {
+    render_message_template(
+        template,
+        &[
+            ("user", user_name),
+            ("channel", channel_name),
+            ("guild", guild_name),
+            ("duration", &format_duration(session_duration)),
+        ],
+    )
+}
+
+pub fn build_voice_activity_message(
+    template: &str,
+    user_name: &str,
+    channel_name: &str,
+    guild_name: &str,
+) -> String {
+    render_message_template(
+        template,
+        &[
+            ("user", user_name),
+            ("channel", channel_name),
+            ("guild", guild_name),
+        ],
+    )
+}
+
+/// Values are copied as they are, placeholders inside them are never expanded.
+fn render_message_template(template: &str, values: &[(&str, &str)]) -> String {
+    let mut message = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(brace_index) = rest.find('{') {
+        message.push_str(&rest[..brace_index]);
+        let after_brace = &rest[brace_index + 1..];
+
+        let placeholder_value = after_brace.find('}').and_then(|end_index| {
+            values
+                .iter()
+                .find(|(placeholder, _)| *placeholder == &after_brace[..end_index])
+                .map(|(_, value)| (end_index, *value))
+        });
+        match placeholder_value {
+            Some((end_index, value)) => {
+                message.push_str(value);
+                rest = &after_brace[end_index + 1..];
+            }
+            None => {
+                message.push('{');
+                rest = after_brace;
+            }
+        }
+    }
+    message.push_str(rest);
+
+    message
+}
+
+fn format_duration(duration: Duration) -> String {
+    let total_seconds = duration.as_secs();
+    let hours = total_seconds / 3600;
+    let minutes = total_seconds % 3600 / 60;
+
+    if hours > 0 {
+        format!("{}h{}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", total_seconds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SESSION_ENDED_TEMPLATE: &str =
+        "Session in *{channel}* in *{guild}* ended after {duration}, last was {user}";
+
+    #[test]
+    fn renders_every_placeholder() {
+        assert_eq!(
+            build_voice_session_ended_message(
+                SESSION_ENDED_TEMPLATE,
+                "alice",
+                "General",
+                "Friends",
+                Duration::from_secs(2 * 3600 + 13 * 60),
+            ),
+            "Session in *General* in *Friends* ended after 2h13m, last was alice"
+        );
+    }
+
+    #[test]
+    fn placeholders_in_values_are_not_expanded() {
+        assert_eq!(
+            build_voice_session_ended_message(
+                SESSION_ENDED_TEMPLATE,
+                "{guild}",
+                "{duration}",
+                "Friends {",
+                Duration::from_secs(45 * 60),
+            ),
+            "Session in *{duration}* in *Friends {* ended after 45m, last was {guild}"
+        );
+    }
+
+    #[test]
+    fn voice_activity_placeholders_in_values_are_not_expanded() {
+        assert_eq!(
+            build_voice_activity_message(
+                "*{user}* started streaming in *{channel}* in *{guild}*",
+                "{channel}",
+                "{user} {guild}",
+                "{}",
+            ),
+            "*{channel}* started streaming in *{user} {guild}* in *{}*"
+        );
+    }
+
+    #[test]
+    fn unknown_placeholders_and_lone_braces_are_kept() {
+        assert_eq!(
+            render_message_template("{unknown} { {user}} {user", &[("user", "alice")]),
+            "{unknown} { alice} {user"
+        );
+    }
+}
@@ -0,0 +1,152 @@
+use prometheus::core::Collector;
+use prometheus::{
+    Encoder, Histogram, HistogramOpts, IntCounter, IntCounterVec, Opts, Registry, TextEncoder,
+};
+use std::sync::LazyLock;
+use std::time::Duration;
+use tracing::error;
+
+const NAMESPACE: &str = "hoseus_bot";
+
+pub const SUCCESS_OUTCOME: &str = "success";
+pub const FAILURE_OUTCOME: &str = "failure";
+
+static METRICS: LazyLock<Metrics> = LazyLock::new(Metrics::new);
+
+struct Metrics {
+    registry: Registry,
+    voice_events_received: IntCounter,
+    voice_events_discarded: IntCounterVec,
+    voice_events_notified: IntCounter,
+    commands: IntCounterVec,
+    telegram_sends: IntCounterVec,
+    telegram_send_duration: Histogram,
+    gateway_reconnects: IntCounterVec,
+}
+
+impl Metrics {
+    fn new() -> Self {
+        let registry = Registry::new();
+
+        Metrics {
+            voice_events_received: register(
+                &registry,
+                IntCounter::with_opts(opts(
+                    "voice_events_received_total",
+                    "Voice state updates received from discord",
+                )),
+            ),
+            voice_events_discarded: register(
+                &registry,
+                IntCounterVec::new(
+                    opts(
+                        "voice_events_discarded_total",
+                        "Voice state updates that did not notify, by reason",
+                    ),
+                    &["reason"],
+                ),
+            ),
+            voice_events_notified: register(
+                &registry,
+                IntCounter::with_opts(opts(
+                    "voice_events_notified_total",
+                    "Voice state updates that dispatched a notification",
+                )),
+            ),
+            commands: register(
+                &registry,
+                IntCounterVec::new(
+                    opts("commands_total", "Slash commands run, by name and outcome"),
+                    &["command", "outcome"],
+                ),
+            ),
+            telegram_sends: register(
+                &registry,
+                IntCounterVec::new(
+                    opts(
+                        "telegram_sends_total",
+                        "Messages sent to telegram, by outcome",
+                    ),
+                    &["outcome"],
+                ),
+            ),
+            telegram_send_duration: register(
+                &registry,
+                Histogram::with_opts(HistogramOpts::from(opts(
+                    "telegram_send_duration_seconds",
+                    "Time taken by telegram to answer a send, failures included",
+                ))),
+            ),
+            gateway_reconnects: register(
+                &registry,
+                IntCounterVec::new(
+                    opts(
+                        "gateway_reconnects_total",
+                        "Discord gateway reconnections, by kind (resume or a new session)",
+                    ),
+                    &["kind"],
+                ),
+            ),
+            registry,
+        }
+    }
+}
+
+fn register<T: Collector + Clone + 'static>(
+    registry: &Registry,
+    collector: prometheus::Result<T>,
+) -> T {
+    let collector = collector.expect("Error. Invalid metric");
+    registry
+        .register(Box::new(collector.clone()))
+        .expect("Error. Could not register metric");
+
+    collector
+}
+
+fn opts(name: &str, help: &str) -> Opts {
+    Opts::new(name, help).namespace(NAMESPACE)
+}
+
+pub fn record_voice_event_received() {
+    METRICS.voice_events_received.inc();
+}
+
+pub fn record_voice_event_discarded(reason: &str) {
+    METRICS
+        .voice_events_discarded
+        .with_label_values(&[reason])
+        .inc();
+}
+
+pub fn record_voice_event_notified() {
+    METRICS.voice_events_notified.inc();
+}
+
+pub fn record_command(command_name: &str, outcome: &str) {
+    METRICS
+        .commands
+        .with_label_values(&[command_name, outcome])
+        .inc();
+}
+
+pub fn record_telegram_send(outcome: &str, duration: Duration) {
+    METRICS.telegram_sends.with_label_values(&[outcome]).inc();
+    METRICS
+        .telegram_send_duration
+        .observe(duration.as_secs_f64());
+}
+
+pub fn record_gateway_reconnect(kind: &str) {
+    METRICS.gateway_reconnects.with_label_values(&[kind]).inc();
+}
+
+pub fn render() -> String {
+    let mut buffer = Vec::new();
+
+    if let Err(why) = TextEncoder::new().encode(&METRICS.registry.gather(), &mut buffer) {
+        error!(trace = ?why, "Could not encode metrics");
+    }
+
+    String::from_utf8(buffer).unwrap_or_default()
+}
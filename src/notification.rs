@@ -0,0 +1,90 @@
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, GuildId};
+use std::ops::Deref;
+use std::sync::LazyLock;
+use std::time::Duration;
+use tracing::info;
+
+use crate::delivery_queue;
+use crate::telegram::TelegramNotificationSink;
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub guild_id: Option<GuildId>,
+    pub channel_id: Option<ChannelId>,
+    pub animation_url: String,
+    pub message: String,
+}
+
+impl Notification {
+    pub fn new(
+        guild_id: Option<GuildId>,
+        channel_id: Option<ChannelId>,
+        animation_url: &str,
+        message: &str,
+    ) -> Self {
+        Notification {
+            guild_id,
+            channel_id,
+            animation_url: animation_url.to_string(),
+            message: message.to_string(),
+        }
+    }
+}
+
+pub enum DeliveryError {
+    Retryable {
+        retry_after: Option<Duration>,
+        trace: String,
+    },
+    Permanent {
+        trace: String,
+    },
+}
+
+#[serenity::async_trait]
+pub trait NotificationSink: Send + Sync {
+    fn name(&self) -> &str;
+
+    fn resolve_targets(&self, notification: &Notification) -> Vec<String>;
+
+    async fn send_notification(
+        &self,
+        target: &str,
+        notification: &Notification,
+    ) -> Result<(), DeliveryError>;
+}
+
+static NOTIFICATION_SINKS: LazyLock<Vec<Box<dyn NotificationSink>>> =
+    LazyLock::new(|| vec![Box::new(TelegramNotificationSink)]);
+
+pub fn find_notification_sink(sink_name: &str) -> Option<&'static dyn NotificationSink> {
+    NOTIFICATION_SINKS
+        .deref()
+        .iter()
+        .find(|notification_sink| notification_sink.name() == sink_name)
+        .map(|notification_sink| notification_sink.as_ref())
+}
+
+pub async fn dispatch_notification(notification: &Notification) -> Result<(), String> {
+    for notification_sink in NOTIFICATION_SINKS.deref() {
+        for target in notification_sink.resolve_targets(notification) {
+            info!(
+                sink_name = notification_sink.name(),
+                target,
+                animation_url = notification.animation_url,
+                caption = notification.message,
+                "Dispatching notification"
+            );
+
+            delivery_queue::enqueue_delivery(
+                notification_sink.as_ref(),
+                target,
+                notification.clone(),
+            )
+            .await?;
+        }
+    }
+
+    Ok(())
+}
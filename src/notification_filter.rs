@@ -0,0 +1,147 @@
+use serde::{Deserialize, Serialize};
+use serenity::all::{ChannelId, GuildId};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::sync::{LazyLock, RwLock};
+use tracing::info;
+
+use crate::{config, file_helper};
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationRule {
+    #[default]
+    Include,
+    Exclude,
+}
+
+impl NotificationRule {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "include" => Some(NotificationRule::Include),
+            "exclude" => Some(NotificationRule::Exclude),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for NotificationRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotificationRule::Include => write!(f, "include"),
+            NotificationRule::Exclude => write!(f, "exclude"),
+        }
+    }
+}
+
+/// The most specific rule wins: channel, AFK channel, category, then the guild default.
+#[derive(Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GuildNotificationFilter {
+    pub default_rule: NotificationRule,
+    pub afk_channel_rule: Option<NotificationRule>,
+    pub category_rules: BTreeMap<ChannelId, NotificationRule>,
+    pub channel_rules: BTreeMap<ChannelId, NotificationRule>,
+}
+
+impl GuildNotificationFilter {
+    pub fn resolve_rule(&self, voice_channel_location: &VoiceChannelLocation) -> NotificationRule {
+        self.channel_rules
+            .get(&voice_channel_location.channel_id)
+            .copied()
+            .or(self
+                .afk_channel_rule
+                .filter(|_| voice_channel_location.is_afk_channel))
+            .or_else(|| {
+                voice_channel_location
+                    .category_id
+                    .and_then(|category_id| self.category_rules.get(&category_id).copied())
+            })
+            .unwrap_or(self.default_rule)
+    }
+}
+
+pub struct VoiceChannelLocation {
+    pub channel_id: ChannelId,
+    pub category_id: Option<ChannelId>,
+    pub is_afk_channel: bool,
+}
+
+static NOTIFICATION_FILTERS: LazyLock<RwLock<BTreeMap<GuildId, GuildNotificationFilter>>> =
+    LazyLock::new(|| RwLock::new(BTreeMap::new()));
+
+pub fn load_notification_filters() -> Result<(), String> {
+    let file_path = config::notification_filter_file();
+
+    let json_string = match fs::read_to_string(&file_path) {
+        Ok(json_string) => json_string,
+        Err(_) => {
+            info!(
+                file_path,
+                "Notification filter file not found, starting empty"
+            );
+            return Ok(());
+        }
+    };
+    let notification_filters: BTreeMap<GuildId, GuildNotificationFilter> =
+        serde_json::from_str(&json_string)
+            .map_err(|why| format!("Unable to parse json in {}. {}", file_path, why))?;
+
+    *NOTIFICATION_FILTERS.write().unwrap() = notification_filters;
+
+    Ok(())
+}
+
+pub fn is_notification_allowed(
+    guild_id: GuildId,
+    voice_channel_location: &VoiceChannelLocation,
+) -> bool {
+    NOTIFICATION_FILTERS
+        .read()
+        .unwrap()
+        .get(&guild_id)
+        .map_or(NotificationRule::Include, |guild_notification_filter| {
+            guild_notification_filter.resolve_rule(voice_channel_location)
+        })
+        == NotificationRule::Include
+}
+
+pub fn get_guild_notification_filter(guild_id: GuildId) -> GuildNotificationFilter {
+    NOTIFICATION_FILTERS
+        .read()
+        .unwrap()
+        .get(&guild_id)
+        .cloned()
+        .unwrap_or_default()
+}
+
+pub fn update_guild_notification_filter(
+    guild_id: GuildId,
+    update: impl FnOnce(&mut GuildNotificationFilter),
+) -> Result<(), String> {
+    let mut notification_filters_guard = NOTIFICATION_FILTERS.write().unwrap();
+
+    let mut notification_filters = notification_filters_guard.clone();
+    let guild_notification_filter = notification_filters.entry(guild_id).or_default();
+    update(guild_notification_filter);
+    if *guild_notification_filter == GuildNotificationFilter::default() {
+        notification_filters.remove(&guild_id);
+    }
+
+    write_notification_filters(&config::notification_filter_file(), &notification_filters)?;
+    *notification_filters_guard = notification_filters;
+
+    Ok(())
+}
+
+fn write_notification_filters(
+    file_path: &str,
+    notification_filters: &BTreeMap<GuildId, GuildNotificationFilter>,
+) -> Result<(), String> {
+    let json_string = serde_json::to_string_pretty(notification_filters)
+        .map_err(|why| format!("Unable to serialize notification filters. {}", why))?;
+
+    file_helper::write_atomically(file_path, json_string.as_bytes())
+        .map_err(|why| format!("Unable to write file {}. {:?}", file_path, why))
+}
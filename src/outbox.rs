@@ -0,0 +1,211 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::iter;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{mpsc, LazyLock, Mutex};
+use std::thread;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::oneshot;
+use tracing::{error, info, warn};
+
+use crate::notification::Notification;
+use crate::{config, file_helper};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub id: u64,
+    pub sink_name: String,
+    pub target: String,
+    pub notification: Notification,
+    pub created_at_unix_seconds: u64,
+}
+
+static OUTBOX_ENTRIES: LazyLock<Mutex<BTreeMap<u64, OutboxEntry>>> =
+    LazyLock::new(|| Mutex::new(BTreeMap::new()));
+
+static NEXT_OUTBOX_ENTRY_ID: AtomicU64 = AtomicU64::new(1);
+
+enum OutboxWrite {
+    Append(OutboxEntry),
+    Rewrite,
+    Flush(oneshot::Sender<()>),
+}
+
+static OUTBOX_WRITER: LazyLock<mpsc::Sender<OutboxWrite>> = LazyLock::new(|| {
+    let (sender, receiver) = mpsc::channel();
+
+    thread::Builder::new()
+        .name("outbox-writer".to_string())
+        .spawn(move || run_outbox_writer(receiver))
+        .expect("Error. Could not start outbox writer");
+
+    sender
+});
+
+pub fn load_pending_entries() -> Vec<OutboxEntry> {
+    let file_path = config::notification_outbox_file();
+
+    let jsonl_string = match fs::read_to_string(&file_path) {
+        Ok(jsonl_string) => jsonl_string,
+        Err(_) => {
+            info!(file_path, "Outbox file not found, starting empty");
+            return Vec::new();
+        }
+    };
+
+    let now_unix_seconds = unix_seconds(SystemTime::now());
+    let max_age_seconds = config::notification_outbox_max_age().as_secs();
+
+    let mut outbox_entries = OUTBOX_ENTRIES.lock().unwrap();
+
+    for line in jsonl_string.lines().filter(|line| !line.trim().is_empty()) {
+        match serde_json::from_str::<OutboxEntry>(line) {
+            Ok(outbox_entry)
+                if now_unix_seconds.saturating_sub(outbox_entry.created_at_unix_seconds)
+                    > max_age_seconds =>
+            {
+                warn!(
+                    outbox_entry_id = outbox_entry.id,
+                    sink_name = outbox_entry.sink_name,
+                    caption = outbox_entry.notification.message,
+                    "Discarded stale outbox entry"
+                );
+            }
+            Ok(outbox_entry) => {
+                outbox_entries.insert(outbox_entry.id, outbox_entry);
+            }
+            Err(why) => {
+                error!(file_path, trace = ?why, "Discarded unparseable outbox entry");
+            }
+        }
+    }
+
+    let next_id = outbox_entries.keys().max().map_or(1, |max_id| max_id + 1);
+    NEXT_OUTBOX_ENTRY_ID.store(next_id, Ordering::SeqCst);
+
+    let pending_entries = outbox_entries.values().cloned().collect();
+    drop(outbox_entries);
+
+    queue_write(OutboxWrite::Rewrite);
+
+    pending_entries
+}
+
+/// Returns once the entry is on disk. A failed write is only logged.
+pub async fn add_entry(sink_name: &str, target: &str, notification: &Notification) -> u64 {
+    let outbox_entry = OutboxEntry {
+        id: NEXT_OUTBOX_ENTRY_ID.fetch_add(1, Ordering::SeqCst),
+        sink_name: sink_name.to_string(),
+        target: target.to_string(),
+        notification: notification.clone(),
+        created_at_unix_seconds: unix_seconds(SystemTime::now()),
+    };
+    let id = outbox_entry.id;
+
+    OUTBOX_ENTRIES
+        .lock()
+        .unwrap()
+        .insert(id, outbox_entry.clone());
+    queue_write(OutboxWrite::Append(outbox_entry));
+    flush().await;
+
+    id
+}
+
+pub fn remove_entry(id: u64) {
+    if OUTBOX_ENTRIES.lock().unwrap().remove(&id).is_some() {
+        queue_write(OutboxWrite::Rewrite);
+    }
+}
+
+#[cfg(test)]
+pub fn entry_ids_with_target(target: &str) -> Vec<u64> {
+    OUTBOX_ENTRIES
+        .lock()
+        .unwrap()
+        .values()
+        .filter(|outbox_entry| outbox_entry.target == target)
+        .map(|outbox_entry| outbox_entry.id)
+        .collect()
+}
+
+pub async fn flush() {
+    let (sender, receiver) = oneshot::channel();
+    queue_write(OutboxWrite::Flush(sender));
+
+    let _ = receiver.await;
+}
+
+fn queue_write(outbox_write: OutboxWrite) {
+    if OUTBOX_WRITER.send(outbox_write).is_err() {
+        error!("Outbox writer stopped. Outbox not persisted");
+    }
+}
+
+/// An entry appended after a rewrite that already had it is a duplicate line, loading keeps one.
+fn run_outbox_writer(receiver: mpsc::Receiver<OutboxWrite>) {
+    while let Ok(first_write) = receiver.recv() {
+        let mut appended_entries = Vec::new();
+        let mut needs_rewrite = false;
+        let mut flush_senders = Vec::new();
+
+        for outbox_write in iter::once(first_write).chain(receiver.try_iter()) {
+            match outbox_write {
+                OutboxWrite::Append(outbox_entry) => appended_entries.push(outbox_entry),
+                OutboxWrite::Rewrite => needs_rewrite = true,
+                OutboxWrite::Flush(flush_sender) => flush_senders.push(flush_sender),
+            }
+        }
+
+        let file_path = config::notification_outbox_file();
+        let write_result = if needs_rewrite {
+            rewrite_outbox(&file_path)
+        } else if !appended_entries.is_empty() {
+            append_outbox_entries(&file_path, &appended_entries)
+        } else {
+            Ok(())
+        };
+        if let Err(why) = write_result {
+            error!(file_path, trace = ?why, "Could not persist outbox");
+        }
+
+        for flush_sender in flush_senders {
+            let _ = flush_sender.send(());
+        }
+    }
+}
+
+fn append_outbox_entries(file_path: &str, outbox_entries: &[OutboxEntry]) -> std::io::Result<()> {
+    let mut file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_path)?;
+
+    file.write_all(&to_jsonl(outbox_entries)?)?;
+    file.sync_data()
+}
+
+fn rewrite_outbox(file_path: &str) -> std::io::Result<()> {
+    let outbox_entries: Vec<OutboxEntry> =
+        OUTBOX_ENTRIES.lock().unwrap().values().cloned().collect();
+
+    file_helper::write_atomically(file_path, &to_jsonl(&outbox_entries)?)
+}
+
+fn to_jsonl(outbox_entries: &[OutboxEntry]) -> std::io::Result<Vec<u8>> {
+    let mut jsonl = Vec::new();
+    for outbox_entry in outbox_entries {
+        serde_json::to_writer(&mut jsonl, outbox_entry)?;
+        jsonl.push(b'\n');
+    }
+
+    Ok(jsonl)
+}
+
+fn unix_seconds(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or(Duration::ZERO)
+        .as_secs()
+}
@@ -0,0 +1,190 @@
+use serenity::all::{GuildId, UserId};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+use crate::config;
+
+static NOTIFY_COMMAND_RATE_LIMITER: LazyLock<RateLimiter> = LazyLock::new(RateLimiter::new);
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RateLimitScope {
+    User(UserId),
+    Guild(GuildId),
+}
+
+impl fmt::Display for RateLimitScope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RateLimitScope::User(user_id) => write!(f, "user {}", user_id),
+            RateLimitScope::Guild(guild_id) => write!(f, "guild {}", guild_id),
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct TokenBucketLimit {
+    pub capacity: u32,
+    pub refill_interval: Duration,
+}
+
+impl TokenBucketLimit {
+    fn full_refill_time(&self) -> Duration {
+        self.refill_interval.saturating_mul(self.capacity)
+    }
+}
+
+pub struct RateLimitRejection {
+    pub scope: RateLimitScope,
+    pub retry_after: Duration,
+}
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+    limit: TokenBucketLimit,
+}
+
+pub struct RateLimiter {
+    token_buckets: Mutex<HashMap<RateLimitScope, TokenBucket>>,
+    rejected_count: AtomicU64,
+}
+
+impl RateLimiter {
+    pub fn new() -> Self {
+        RateLimiter {
+            token_buckets: Mutex::new(HashMap::new()),
+            rejected_count: AtomicU64::new(0),
+        }
+    }
+
+    /// Takes a token from every scope, or from none if any bucket is empty.
+    pub fn try_acquire(
+        &self,
+        scopes: &[(RateLimitScope, TokenBucketLimit)],
+    ) -> Result<(), RateLimitRejection> {
+        let now = Instant::now();
+        let mut token_buckets = self.token_buckets.lock().unwrap();
+
+        for (scope, limit) in scopes {
+            if limit.capacity == 0 {
+                continue;
+            }
+
+            let token_bucket = token_buckets.entry(*scope).or_insert(TokenBucket {
+                tokens: f64::from(limit.capacity),
+                last_refill: now,
+                limit: *limit,
+            });
+            token_bucket.limit = *limit;
+            refill(token_bucket, now);
+
+            if token_bucket.tokens < 1.0 {
+                self.rejected_count.fetch_add(1, Ordering::Relaxed);
+                return Err(RateLimitRejection {
+                    scope: *scope,
+                    retry_after: limit.refill_interval.mul_f64(1.0 - token_bucket.tokens),
+                });
+            }
+        }
+
+        for (scope, limit) in scopes {
+            if let Some(token_bucket) = token_buckets.get_mut(scope) {
+                if limit.capacity > 0 {
+                    token_bucket.tokens -= 1.0;
+                }
+            }
+        }
+
+        // Each bucket is pruned by its own limit, other calls may use slower ones.
+        token_buckets.retain(|_, token_bucket| {
+            now.duration_since(token_bucket.last_refill) < token_bucket.limit.full_refill_time()
+        });
+
+        Ok(())
+    }
+
+    pub fn rejected_count(&self) -> u64 {
+        self.rejected_count.load(Ordering::Relaxed)
+    }
+}
+
+fn refill(token_bucket: &mut TokenBucket, now: Instant) {
+    let limit = token_bucket.limit;
+    let elapsed = now.duration_since(token_bucket.last_refill);
+    let refilled_tokens = elapsed.as_secs_f64() / limit.refill_interval.as_secs_f64();
+
+    token_bucket.tokens = (token_bucket.tokens + refilled_tokens).min(f64::from(limit.capacity));
+    token_bucket.last_refill = now;
+}
+
+pub fn try_acquire_notify_command(
+    user_id: UserId,
+    guild_id: Option<GuildId>,
+) -> Result<(), RateLimitRejection> {
+    let mut scopes = vec![(
+        RateLimitScope::User(user_id),
+        config::notify_rate_limit_user_limit(),
+    )];
+    if let Some(guild_id) = guild_id {
+        scopes.push((
+            RateLimitScope::Guild(guild_id),
+            config::notify_rate_limit_guild_limit(),
+        ));
+    }
+
+    NOTIFY_COMMAND_RATE_LIMITER.try_acquire(&scopes)
+}
+
+pub fn notify_command_rejected_count() -> u64 {
+    NOTIFY_COMMAND_RATE_LIMITER.rejected_count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::thread;
+
+    #[test]
+    fn fast_refill_call_keeps_slow_refill_buckets() {
+        let rate_limiter = RateLimiter::new();
+        let guild_scope = RateLimitScope::Guild(GuildId::new(1));
+        let user_scope = RateLimitScope::User(UserId::new(2));
+        let slow_limit = TokenBucketLimit {
+            capacity: 1,
+            refill_interval: Duration::from_secs(300),
+        };
+        let fast_limit = TokenBucketLimit {
+            capacity: 1,
+            refill_interval: Duration::from_millis(10),
+        };
+
+        assert!(rate_limiter
+            .try_acquire(&[(guild_scope, slow_limit)])
+            .is_ok());
+        thread::sleep(Duration::from_millis(20));
+        assert!(rate_limiter
+            .try_acquire(&[(user_scope, fast_limit)])
+            .is_ok());
+
+        assert!(rate_limiter
+            .try_acquire(&[(guild_scope, slow_limit)])
+            .is_err());
+        assert_eq!(rate_limiter.rejected_count(), 1);
+    }
+
+    #[test]
+    fn huge_capacity_does_not_overflow_refill_time() {
+        let rate_limiter = RateLimiter::new();
+        let limit = TokenBucketLimit {
+            capacity: u32::MAX,
+            refill_interval: Duration::from_secs(u64::MAX / 2),
+        };
+
+        assert!(rate_limiter
+            .try_acquire(&[(RateLimitScope::User(UserId::new(1)), limit)])
+            .is_ok());
+    }
+}
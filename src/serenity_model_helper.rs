@@ -1,6 +1,9 @@
 use serenity::all::{
     ChannelId, CommandInteraction, Context, GuildChannel, GuildId, UserId, VoiceState,
 };
+use tracing::warn;
+
+use crate::notification_filter::VoiceChannelLocation;
 
 pub const NOT_OBTAINED_STRING: &str = "<not_obtained>";
 
@@ -40,19 +43,28 @@ async fn get_guild_channel(ctx: &Context, channel_id: Option<&ChannelId>) -> Opt
             .to_channel(ctx)
             .await
             .ok()
-            .map(|channel| channel.guild())
-            .flatten(),
+            .and_then(|channel| channel.guild()),
         None => None,
     }
 }
 
-async fn get_voice_channel_members_count(ctx: &Context, channel_id: Option<&ChannelId>) -> usize {
-    match get_guild_channel(ctx, channel_id).await {
-        Some(some_guild_channel) => some_guild_channel
-            .members(ctx)
-            .map(|members| members.len())
-            .unwrap(),
-        None => 0,
+/// None when unknown, which is not the same as empty.
+async fn get_voice_channel_members_count(
+    ctx: &Context,
+    channel_id: Option<&ChannelId>,
+) -> Option<usize> {
+    let some_guild_channel = get_guild_channel(ctx, channel_id).await?;
+
+    match some_guild_channel.members(ctx) {
+        Ok(members) => Some(members.len()),
+        Err(why) => {
+            warn!(
+                channel_id = %some_guild_channel.id,
+                trace = ?why,
+                "Could not get voice channel members from cache"
+            );
+            None
+        }
     }
 }
 
@@ -71,10 +83,47 @@ pub async fn get_guild_name_from_voice_state(ctx: &Context, voice_state: &VoiceS
 pub async fn get_voice_channel_members_count_from_voice_state(
     ctx: &Context,
     voice_state: &VoiceState,
-) -> usize {
+) -> Option<usize> {
     get_voice_channel_members_count(ctx, voice_state.channel_id.as_ref()).await
 }
 
+pub async fn get_channel_name_from_channel_id(ctx: &Context, channel_id: &ChannelId) -> String {
+    get_channel_name(ctx, Some(channel_id)).await
+}
+
+pub async fn get_voice_channel_members_count_from_channel_id(
+    ctx: &Context,
+    channel_id: &ChannelId,
+) -> Option<usize> {
+    get_voice_channel_members_count(ctx, Some(channel_id)).await
+}
+
+pub fn get_voice_channel_location(
+    ctx: &Context,
+    guild_id: GuildId,
+    channel_id: ChannelId,
+) -> VoiceChannelLocation {
+    let (category_id, afk_channel_id) = match ctx.cache.guild(guild_id) {
+        Some(guild) => (
+            guild
+                .channels
+                .get(&channel_id)
+                .and_then(|guild_channel| guild_channel.parent_id),
+            guild
+                .afk_metadata
+                .as_ref()
+                .map(|afk_metadata| afk_metadata.afk_channel_id),
+        ),
+        None => (None, None),
+    };
+
+    VoiceChannelLocation {
+        channel_id,
+        category_id,
+        is_afk_channel: afk_channel_id == Some(channel_id),
+    }
+}
+
 pub async fn get_channel_name_from_application_command(
     ctx: &Context,
     command: &CommandInteraction,
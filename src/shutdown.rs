@@ -0,0 +1,26 @@
+use tokio::signal::unix::{signal, SignalKind};
+use tracing::error;
+
+pub async fn wait_for_shutdown_signal() -> &'static str {
+    let mut sigterm = match signal(SignalKind::terminate()) {
+        Ok(sigterm) => Some(sigterm),
+        Err(why) => {
+            error!(trace = ?why, "Could not listen for SIGTERM");
+            None
+        }
+    };
+
+    let sigterm_received = async {
+        match sigterm.as_mut() {
+            Some(sigterm) => {
+                sigterm.recv().await;
+            }
+            None => std::future::pending().await,
+        }
+    };
+
+    tokio::select! {
+        _ = sigterm_received => "SIGTERM",
+        _ = tokio::signal::ctrl_c() => "SIGINT",
+    }
+}
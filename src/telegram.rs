@@ -1,38 +1,149 @@
 use frankenstein::api_params::FileUpload;
-use frankenstein::Api as FrankensteinApi;
-use frankenstein::{SendAnimationParams, TelegramApi};
+use frankenstein::AsyncApi as FrankensteinApi;
+use frankenstein::{AsyncTelegramApi, Error as FrankensteinError, SendAnimationParams};
+use serenity::all::{ChannelId, GuildId};
 use std::ops::Deref;
 use std::sync::LazyLock;
+use std::time::{Duration, Instant};
+use tracing::{info, warn};
 
-use crate::config;
+use crate::notification::{DeliveryError, Notification, NotificationSink};
+use crate::{config, metrics};
 
-static FRANKENSTEIN_API: LazyLock<FrankensteinApi> =
-    LazyLock::new(|| FrankensteinApi::new(config::telegram_bot_token().as_str()));
+pub const SINK_NAME: &str = "telegram";
 
-pub fn send_notification_to_telegram(animation_url: &str, message: &str) {
+const TOO_MANY_REQUESTS_ERROR_CODE: u64 = 429;
+const SERVER_ERROR_CODES_START: u64 = 500;
+
+static FRANKENSTEIN_API: LazyLock<FrankensteinApi> = LazyLock::new(|| {
+    let http_client = reqwest::Client::builder()
+        .connect_timeout(config::telegram_connect_timeout())
+        .timeout(config::telegram_request_timeout())
+        .build()
+        .expect("Error. Could not create telegram http client");
+
+    FrankensteinApi::builder()
+        .api_url(format!(
+            "{}/bot{}",
+            config::telegram_api_base_url(),
+            config::telegram_bot_token()
+        ))
+        .client(http_client)
+        .build()
+});
+
+pub struct TelegramNotificationSink;
+
+#[serenity::async_trait]
+impl NotificationSink for TelegramNotificationSink {
+    fn name(&self) -> &str {
+        SINK_NAME
+    }
+
+    fn resolve_targets(&self, notification: &Notification) -> Vec<String> {
+        resolve_telegram_chat_ids(notification.guild_id, notification.channel_id)
+    }
+
+    async fn send_notification(
+        &self,
+        target: &str,
+        notification: &Notification,
+    ) -> Result<(), DeliveryError> {
+        send_notification_to_telegram(target, &notification.animation_url, &notification.message)
+            .await
+    }
+}
+
+fn resolve_telegram_chat_ids(
+    guild_id: Option<GuildId>,
+    channel_id: Option<ChannelId>,
+) -> Vec<String> {
+    let telegram_chat_routes = config::telegram_chat_routes();
+
+    let channel_route = telegram_chat_routes.iter().find(|route| {
+        Some(route.guild_id) == guild_id
+            && route.channel_id.is_some()
+            && route.channel_id == channel_id
+    });
+    let guild_route = telegram_chat_routes
+        .iter()
+        .find(|route| Some(route.guild_id) == guild_id && route.channel_id.is_none());
+
+    match channel_route.or(guild_route) {
+        Some(route) => route.chat_ids.clone(),
+        None => vec![config::telegram_chat_id()],
+    }
+}
+
+#[tracing::instrument(skip(animation_url, message))]
+async fn send_notification_to_telegram(
+    chat_id: &str,
+    animation_url: &str,
+    message: &str,
+) -> Result<(), DeliveryError> {
     let send_animation_params: SendAnimationParams = SendAnimationParams::builder()
-        .chat_id(config::telegram_chat_id())
+        .chat_id(chat_id.to_string())
         .animation(FileUpload::String(animation_url.to_string()))
         .caption(message)
         .build();
 
-    println!(
-        "Start. Sending to telegram. Animation url: {}. Caption: {}",
-        animation_url, message
-    );
+    info!(animation_url, caption = message, "Sending to telegram");
+    let started_at = Instant::now();
 
-    if let Err(why) = FRANKENSTEIN_API
+    let send_animation_result = FRANKENSTEIN_API
         .deref()
         .send_animation(&send_animation_params)
-    {
-        println!(
-            "Error. Could not send message to telegram. Trace: {:?}",
-            why
-        );
-    } else {
-        println!(
-            "End. Sent to telegram. Animation url: {}. Caption: {}",
-            animation_url, message
-        );
+        .await;
+
+    let latency = started_at.elapsed();
+    let latency_ms = latency.as_millis();
+    if let Err(why) = send_animation_result {
+        warn!(trace = ?why, latency_ms, "Could not send message to telegram");
+        metrics::record_telegram_send(metrics::FAILURE_OUTCOME, latency);
+
+        return Err(to_delivery_error(why));
+    }
+
+    info!(latency_ms, "Sent to telegram");
+    metrics::record_telegram_send(metrics::SUCCESS_OUTCOME, latency);
+
+    Ok(())
+}
+
+pub async fn check_telegram_reachable() -> Result<(), String> {
+    FRANKENSTEIN_API
+        .deref()
+        .get_me()
+        .await
+        .map(|_| ())
+        .map_err(|why| format!("{:?}", why))
+}
+
+/// A 4xx never succeeds, only rate limits, server and transport errors are retried.
+fn to_delivery_error(error: FrankensteinError) -> DeliveryError {
+    let trace = format!("{:?}", error);
+
+    match error {
+        FrankensteinError::Api(error_response)
+            if error_response.error_code == TOO_MANY_REQUESTS_ERROR_CODE =>
+        {
+            DeliveryError::Retryable {
+                retry_after: error_response
+                    .parameters
+                    .and_then(|parameters| parameters.retry_after)
+                    .map(|retry_after| Duration::from_secs(u64::from(retry_after))),
+                trace,
+            }
+        }
+        FrankensteinError::Api(error_response)
+            if error_response.error_code < SERVER_ERROR_CODES_START =>
+        {
+            DeliveryError::Permanent { trace }
+        }
+        FrankensteinError::Encode(_) => DeliveryError::Permanent { trace },
+        _ => DeliveryError::Retryable {
+            retry_after: None,
+            trace,
+        },
     }
 }
@@ -0,0 +1,205 @@
+//! Setup shared by the tests that need the configuration, the delivery queue or telegram. It
+//! all lives on its own runtime, so it outlives the runtime of the test that started it.
+
+use axum::extract::State;
+use axum::http::{header, StatusCode};
+use axum::response::IntoResponse;
+use axum::Router;
+use std::collections::HashMap;
+use std::sync::{Arc, LazyLock, Mutex};
+use std::time::{Duration, Instant};
+use std::{env, fs, process};
+use tokio::runtime::Runtime;
+
+use crate::{config, delivery_queue};
+
+pub const MAX_DELIVERY_ATTEMPTS: u32 = 3;
+pub const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+pub const RETRY_MAX_DELAY: Duration = Duration::from_secs(2);
+
+/// What the telegram mock answers for a chat. Each test uses chats of its own, so tests running
+/// at the same time do not see each other's requests.
+#[derive(Clone, Copy)]
+pub enum MockTelegramResponse {
+    Sent,
+    ServerError,
+    TooManyRequests { retry_after_seconds: u32 },
+    BadRequest,
+}
+
+#[derive(Default)]
+pub struct MockTelegram {
+    responses: Mutex<HashMap<String, MockTelegramResponse>>,
+    requests: Mutex<HashMap<String, Vec<Instant>>>,
+}
+
+impl MockTelegram {
+    pub fn respond_to_chat(&self, chat_id: &str, response: MockTelegramResponse) {
+        self.responses
+            .lock()
+            .unwrap()
+            .insert(chat_id.to_string(), response);
+    }
+
+    /// When each request for the chat arrived, oldest first.
+    pub fn requests_to_chat(&self, chat_id: &str) -> Vec<Instant> {
+        self.requests
+            .lock()
+            .unwrap()
+            .get(chat_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+struct TestEnvironment {
+    _runtime: Runtime,
+    mock_telegram: Arc<MockTelegram>,
+}
+
+static TEST_ENVIRONMENT: LazyLock<TestEnvironment> = LazyLock::new(|| {
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .enable_all()
+        .build()
+        .expect("Error. Could not create test runtime");
+
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").expect("Error. Could not bind mock");
+    listener.set_nonblocking(true).unwrap();
+    let mock_address = listener.local_addr().unwrap();
+
+    let mock_telegram = Arc::new(MockTelegram::default());
+    let router = Router::new()
+        .fallback(handle_telegram_request)
+        .with_state(mock_telegram.clone());
+    runtime.spawn(async move {
+        let listener = tokio::net::TcpListener::from_std(listener).unwrap();
+        axum::serve(listener, router).await.unwrap();
+    });
+
+    let test_directory = env::temp_dir().join(format!("hoseus_bot_test_{}", process::id()));
+    fs::create_dir_all(&test_directory).unwrap();
+    let outbox_file = test_directory.join("notification_outbox.jsonl");
+    let _ = fs::remove_file(&outbox_file);
+    let config_file = test_directory.join("config.toml");
+    fs::write(
+        &config_file,
+        format!(
+            r#"
+[discord]
+bot_token = "discord-token"
+
+[telegram]
+bot_token = "1:telegram-token"
+chat_id = "default-chat"
+invite_link = "https://t.me/+invite"
+api_base_url = "http://{}"
+
+[notification]
+max_delivery_attempts = {}
+retry_base_delay_seconds = {}
+retry_max_delay_seconds = {}
+outbox_file = "{}"
+"#,
+            mock_address,
+            MAX_DELIVERY_ATTEMPTS,
+            RETRY_BASE_DELAY.as_secs(),
+            RETRY_MAX_DELAY.as_secs(),
+            outbox_file.display()
+        ),
+    )
+    .unwrap();
+
+    env::set_var("HOSEUS_BOT_CONFIG_FILE", &config_file);
+    config::load_config().expect("Error. Could not load test config");
+
+    let runtime_guard = runtime.enter();
+    delivery_queue::start_delivery_queue();
+    drop(runtime_guard);
+
+    TestEnvironment {
+        _runtime: runtime,
+        mock_telegram,
+    }
+});
+
+/// Loads the test configuration and starts the delivery queue, once for every test.
+pub fn init() {
+    LazyLock::force(&TEST_ENVIRONMENT);
+}
+
+pub fn mock_telegram() -> &'static MockTelegram {
+    &TEST_ENVIRONMENT.mock_telegram
+}
+
+/// Polls until `condition` holds, panicking after `timeout`.
+pub async fn wait_until(timeout: Duration, condition: impl Fn() -> bool) {
+    let deadline = Instant::now() + timeout;
+    while !condition() {
+        assert!(
+            Instant::now() < deadline,
+            "Condition not met in {:?}",
+            timeout
+        );
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+}
+
+async fn handle_telegram_request(
+    State(mock_telegram): State<Arc<MockTelegram>>,
+    body: String,
+) -> impl IntoResponse {
+    let params: serde_json::Value = serde_json::from_str(&body).unwrap_or_default();
+    let chat_id = params["chat_id"]
+        .as_str()
+        .map(str::to_string)
+        .unwrap_or_else(|| params["chat_id"].to_string());
+
+    mock_telegram
+        .requests
+        .lock()
+        .unwrap()
+        .entry(chat_id.clone())
+        .or_default()
+        .push(Instant::now());
+
+    let response = mock_telegram
+        .responses
+        .lock()
+        .unwrap()
+        .get(&chat_id)
+        .copied()
+        .unwrap_or(MockTelegramResponse::Sent);
+
+    let (status_code, body) = match response {
+        MockTelegramResponse::Sent => (
+            StatusCode::OK,
+            r#"{"ok":true,"result":{"message_id":1,"date":0,"chat":{"id":1,"type":"private"}}}"#
+                .to_string(),
+        ),
+        MockTelegramResponse::ServerError => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            r#"{"ok":false,"error_code":500,"description":"Internal Server Error"}"#.to_string(),
+        ),
+        MockTelegramResponse::TooManyRequests {
+            retry_after_seconds,
+        } => (
+            StatusCode::TOO_MANY_REQUESTS,
+            format!(
+                r#"{{"ok":false,"error_code":429,"description":"Too Many Requests","parameters":{{"retry_after":{}}}}}"#,
+                retry_after_seconds
+            ),
+        ),
+        MockTelegramResponse::BadRequest => (
+            StatusCode::BAD_REQUEST,
+            r#"{"ok":false,"error_code":400,"description":"Bad Request: chat not found"}"#
+                .to_string(),
+        ),
+    };
+
+    (
+        status_code,
+        [(header::CONTENT_TYPE, "application/json")],
+        body,
+    )
+}
@@ -0,0 +1,23 @@
+use serenity::all::ChannelId;
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Duration, Instant};
+
+/// Channels already occupied when the bot connected are not tracked.
+static VOICE_SESSION_STARTS: LazyLock<Mutex<HashMap<ChannelId, Instant>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+pub fn start_voice_session(channel_id: ChannelId) {
+    VOICE_SESSION_STARTS
+        .lock()
+        .unwrap()
+        .insert(channel_id, Instant::now());
+}
+
+pub fn end_voice_session(channel_id: ChannelId) -> Option<Duration> {
+    VOICE_SESSION_STARTS
+        .lock()
+        .unwrap()
+        .remove(&channel_id)
+        .map(|started_at| started_at.elapsed())
+}
@@ -0,0 +1,348 @@
+use serenity::all::{ChannelId, Guild, GuildId, UserId, VoiceState};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{LazyLock, Mutex};
+
+/// Fallback for updates without an old voice state in the cache.
+static LAST_VOICE_CHANNELS: LazyLock<Mutex<HashMap<(GuildId, UserId), ChannelId>>> =
+    LazyLock::new(|| Mutex::new(HashMap::new()));
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoiceTransition {
+    Joined,
+    Left,
+    Moved,
+    Muted,
+    Unmuted,
+    Deafened,
+    Undeafened,
+    StreamingStarted,
+    StreamingStopped,
+    VideoStarted,
+    VideoStopped,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoiceAction {
+    EndSession,
+    NotifyJoin,
+    NotifyActivity(VoiceActivity),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VoiceActivity {
+    Stream,
+    Video,
+}
+
+impl fmt::Display for VoiceActivity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VoiceActivity::Stream => write!(f, "stream"),
+            VoiceActivity::Video => write!(f, "video"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+struct VoiceFlags {
+    muted: bool,
+    deafened: bool,
+    streaming: bool,
+    video: bool,
+}
+
+impl From<&VoiceState> for VoiceFlags {
+    fn from(voice_state: &VoiceState) -> Self {
+        VoiceFlags {
+            muted: voice_state.self_mute || voice_state.mute,
+            deafened: voice_state.self_deaf || voice_state.deaf,
+            streaming: voice_state.self_stream.unwrap_or(false),
+            video: voice_state.self_video,
+        }
+    }
+}
+
+pub struct VoiceStateChange {
+    pub previous_channel_id: Option<ChannelId>,
+    pub transitions: Vec<VoiceTransition>,
+}
+
+impl VoiceStateChange {
+    pub fn actions(&self) -> Vec<VoiceAction> {
+        let mut actions: Vec<VoiceAction> = Vec::new();
+        for action in self
+            .transitions
+            .iter()
+            .flat_map(|transition| voice_actions(*transition))
+        {
+            if !actions.contains(action) {
+                actions.push(*action);
+            }
+        }
+
+        actions
+    }
+}
+
+pub fn voice_actions(transition: VoiceTransition) -> &'static [VoiceAction] {
+    match transition {
+        VoiceTransition::Joined => &[VoiceAction::NotifyJoin],
+        VoiceTransition::Left => &[VoiceAction::EndSession],
+        VoiceTransition::Moved => &[VoiceAction::EndSession, VoiceAction::NotifyJoin],
+        VoiceTransition::StreamingStarted => &[VoiceAction::NotifyActivity(VoiceActivity::Stream)],
+        VoiceTransition::VideoStarted => &[VoiceAction::NotifyActivity(VoiceActivity::Video)],
+        VoiceTransition::Muted
+        | VoiceTransition::Unmuted
+        | VoiceTransition::Deafened
+        | VoiceTransition::Undeafened
+        | VoiceTransition::StreamingStopped
+        | VoiceTransition::VideoStopped => &[],
+    }
+}
+
+pub fn record_guild_voice_states(guild: &Guild) {
+    let mut last_voice_channels = LAST_VOICE_CHANNELS.lock().unwrap();
+
+    last_voice_channels.retain(|(guild_id, _), _| *guild_id != guild.id);
+    for voice_state in guild.voice_states.values() {
+        if let Some(channel_id) = voice_state.channel_id {
+            last_voice_channels.insert((guild.id, voice_state.user_id), channel_id);
+        }
+    }
+}
+
+pub fn classify_voice_state_update(
+    old_voice_state: Option<&VoiceState>,
+    new_voice_state: &VoiceState,
+) -> VoiceStateChange {
+    let last_channel_id = record_voice_channel(new_voice_state);
+
+    let (previous_channel_id, previous_flags) = match old_voice_state {
+        Some(old_voice_state) => (
+            old_voice_state.channel_id,
+            Some(VoiceFlags::from(old_voice_state)),
+        ),
+        None => (last_channel_id, None),
+    };
+
+    VoiceStateChange {
+        previous_channel_id,
+        transitions: classify(
+            previous_channel_id,
+            previous_flags,
+            new_voice_state.channel_id,
+            VoiceFlags::from(new_voice_state),
+        ),
+    }
+}
+
+fn record_voice_channel(voice_state: &VoiceState) -> Option<ChannelId> {
+    let guild_id = voice_state.guild_id?;
+    let key = (guild_id, voice_state.user_id);
+    let mut last_voice_channels = LAST_VOICE_CHANNELS.lock().unwrap();
+
+    match voice_state.channel_id {
+        Some(channel_id) => last_voice_channels.insert(key, channel_id),
+        None => last_voice_channels.remove(&key),
+    }
+}
+
+fn classify(
+    previous_channel_id: Option<ChannelId>,
+    previous_flags: Option<VoiceFlags>,
+    channel_id: Option<ChannelId>,
+    flags: VoiceFlags,
+) -> Vec<VoiceTransition> {
+    match (previous_channel_id, channel_id) {
+        (None, None) => Vec::new(),
+        (None, Some(_)) => vec![VoiceTransition::Joined],
+        (Some(_), None) => vec![VoiceTransition::Left],
+        (Some(previous_channel_id), Some(channel_id)) if previous_channel_id != channel_id => {
+            vec![VoiceTransition::Moved]
+        }
+        (Some(_), Some(_)) => match previous_flags {
+            Some(previous_flags) => classify_flags(previous_flags, flags),
+            None => Vec::new(),
+        },
+    }
+}
+
+fn classify_flags(previous_flags: VoiceFlags, flags: VoiceFlags) -> Vec<VoiceTransition> {
+    let flag_transitions = [
+        (
+            previous_flags.muted,
+            flags.muted,
+            VoiceTransition::Muted,
+            VoiceTransition::Unmuted,
+        ),
+        (
+            previous_flags.deafened,
+            flags.deafened,
+            VoiceTransition::Deafened,
+            VoiceTransition::Undeafened,
+        ),
+        (
+            previous_flags.streaming,
+            flags.streaming,
+            VoiceTransition::StreamingStarted,
+            VoiceTransition::StreamingStopped,
+        ),
+        (
+            previous_flags.video,
+            flags.video,
+            VoiceTransition::VideoStarted,
+            VoiceTransition::VideoStopped,
+        ),
+    ];
+
+    flag_transitions
+        .into_iter()
+        .filter(|(was_set, is_set, _, _)| was_set != is_set)
+        .map(|(_, is_set, set_transition, unset_transition)| {
+            if is_set {
+                set_transition
+            } else {
+                unset_transition
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const IDLE: VoiceFlags = VoiceFlags {
+        muted: false,
+        deafened: false,
+        streaming: false,
+        video: false,
+    };
+
+    fn channel(id: u64) -> Option<ChannelId> {
+        Some(ChannelId::new(id))
+    }
+
+    #[test]
+    fn classify_channel_changes() {
+        let cases = [
+            (None, Some(IDLE), None, vec![]),
+            (None, None, channel(1), vec![VoiceTransition::Joined]),
+            (channel(1), Some(IDLE), None, vec![VoiceTransition::Left]),
+            (
+                channel(1),
+                Some(IDLE),
+                channel(2),
+                vec![VoiceTransition::Moved],
+            ),
+            // Cache miss: only the last channel is known, so no flag changes are reported.
+            (channel(1), None, channel(1), vec![]),
+            (channel(1), Some(IDLE), channel(1), vec![]),
+        ];
+
+        for (previous_channel_id, previous_flags, channel_id, expected_transitions) in cases {
+            assert_eq!(
+                classify(previous_channel_id, previous_flags, channel_id, IDLE),
+                expected_transitions,
+                "{:?} -> {:?}",
+                previous_channel_id,
+                channel_id
+            );
+        }
+    }
+
+    #[test]
+    fn classify_flag_flips() {
+        let cases = [
+            (
+                VoiceFlags {
+                    muted: true,
+                    ..IDLE
+                },
+                VoiceTransition::Muted,
+                VoiceTransition::Unmuted,
+            ),
+            (
+                VoiceFlags {
+                    deafened: true,
+                    ..IDLE
+                },
+                VoiceTransition::Deafened,
+                VoiceTransition::Undeafened,
+            ),
+            (
+                VoiceFlags {
+                    streaming: true,
+                    ..IDLE
+                },
+                VoiceTransition::StreamingStarted,
+                VoiceTransition::StreamingStopped,
+            ),
+            (
+                VoiceFlags {
+                    video: true,
+                    ..IDLE
+                },
+                VoiceTransition::VideoStarted,
+                VoiceTransition::VideoStopped,
+            ),
+        ];
+
+        for (flags, set_transition, unset_transition) in cases {
+            assert_eq!(
+                classify(channel(1), Some(IDLE), channel(1), flags),
+                vec![set_transition]
+            );
+            assert_eq!(
+                classify(channel(1), Some(flags), channel(1), IDLE),
+                vec![unset_transition]
+            );
+        }
+    }
+
+    #[test]
+    fn classify_several_flag_flips_at_once() {
+        let flags = VoiceFlags {
+            muted: true,
+            deafened: true,
+            ..IDLE
+        };
+
+        assert_eq!(
+            classify(channel(1), Some(IDLE), channel(1), flags),
+            vec![VoiceTransition::Muted, VoiceTransition::Deafened]
+        );
+    }
+
+    #[test]
+    fn moved_ends_session_and_notifies_join() {
+        assert_eq!(
+            voice_actions(VoiceTransition::Moved),
+            [VoiceAction::EndSession, VoiceAction::NotifyJoin]
+        );
+    }
+
+    #[test]
+    fn actions_are_deduplicated() {
+        let voice_state_change = VoiceStateChange {
+            previous_channel_id: channel(1),
+            transitions: vec![
+                VoiceTransition::Joined,
+                VoiceTransition::Moved,
+                VoiceTransition::StreamingStarted,
+                VoiceTransition::Muted,
+                VoiceTransition::Left,
+            ],
+        };
+
+        assert_eq!(
+            voice_state_change.actions(),
+            vec![
+                VoiceAction::NotifyJoin,
+                VoiceAction::EndSession,
+                VoiceAction::NotifyActivity(VoiceActivity::Stream),
+            ]
+        );
+    }
+}